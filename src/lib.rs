@@ -1,21 +1,88 @@
 extern crate proc_macro;
-use proc_macro::{token_stream::IntoIter, Delimiter, Group, Spacing, TokenStream, TokenTree};
+use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use std::convert::TryFrom;
 use std::iter::FromIterator;
+use tt_equal_core::{
+    get_next_joint_token, get_next_joint_token_in_mode, parse_integer_literal, token_strings,
+    tokens_equal, tokens_equal_in_mode, Mode,
+};
 
 ///
 /// A predicate for whether two token trees are equal.
 /// <sup>**[[tt-call](https://docs.rs/tt-call/)]**</sup>
 ///
 /// Given two token trees, it compares them and returns whether they are equal.
+/// Groups are equal if they have the same delimiter and their contents are equal, so whitespace
+/// inside groups is irrelevant, while e.g. `(a)` and `[a]` are not equal.
+/// Multi-character punctuation is compared as a unit, so e.g. `+=` and `+ =` are not equal.
+/// Input ending in a joint punctuation, e.g. the `=` of a `$op:tt` that `macro_rules!` was given
+/// as `=;`, results in a `compile_error!`, as it looks like an incomplete multi-character
+/// punctuation.
+/// A `-` or `+` followed by a number is two token trees, unless in the `signed_numbers` mode
+/// (see [Modes](#modes)).
+/// A group without delimiters holding a single token tree, as `macro_rules!` may produce when
+/// passing on a metavariable, is compared like the token tree it holds.
+/// Since only tokens are compared, input using unstable syntax, e.g. the `macro` definitions of
+/// declarative macros 2.0, is compared like any other input, with `$x` and `$x:expr` being
+/// the tokens `$` `x` and `$` `x` `:` `expr`.
 /// Intended for use with [tt_if](https://docs.rs/tt-call/1.0.6/tt_call/macro.tt_if.html).
 ///
 /// # Input
 ///
 /// - `input = [{` exactly two token trees `}]`
+/// - `mode = [{` optionally, the modes to compare in (see [Modes](#modes)) `}]`
+/// - `defaults = [{` optionally, more modes to compare in, e.g. a preset fixed by a wrapping
+///   macro, which `mode` then adds to `}]`
+/// - `max_tokens = [{` optionally, the maximum number of tokens the input may contain,
+///   100000 by default `}]`
+/// - `emit_applied = [{` optionally, either true or false, whether to also output which modes
+///   the result depends on, false by default `}]`
+/// - `echo = [{` optionally, either true or false, whether to also output the compared
+///   token tree, false by default `}]`
+/// - `expected_type = [{` optionally, a fixed-width integer type, e.g. `u8`, that integer
+///   literals are expected to fit in `}]`
+/// - `max_depth = [{` optionally, the number of levels of nested groups whose contents are
+///   compared `}]`
+/// - `self_type = [{` optionally, the type that `Self` stands for `}]`
+///
+/// Tokens nested in groups, as well as the groups themselves, count towards `max_tokens`.
+/// If the input contains more tokens, a `compile_error!` is emitted instead of comparing it,
+/// protecting against pathological inputs causing long compile times.
 ///
 /// # Output
 ///
 /// - `is_equal = [{` either true or false `}]`
+/// - `applied = [{` only if `emit_applied` is true, the names of the given modes without
+///   which the result would be different, in the order they are listed in under
+///   [Modes](#modes) `}]`
+/// - `canonical = [{` only if `echo` is true, the first of the two token trees if they are
+///   equal, otherwise nothing `}]`
+///
+/// E.g. comparing `1_000` and `0x3E8` in the modes `semantic case_insensitive` outputs
+/// `applied = [{ semantic }]`, since the two are only equal because of the semantic mode.
+/// If the result doesn't depend on any single mode, e.g. because two modes would each make
+/// the sides equal on their own, `applied` is empty.
+///
+/// `canonical` holds the token tree itself rather than its source text, so the whitespace of
+/// the input doesn't carry over, allowing later macros to reuse the compared tokens.
+///
+/// If `expected_type` is given, sides that are integer literals, optionally preceded by `-` in
+/// the `signed_numbers` mode, are compared by their value, ignoring their spelling and type suffix, so e.g. with
+/// `expected_type = [{ u8 }]` the literals `255` and `0xFF` are equal. A literal that doesn't
+/// fit in the type results in a `compile_error!` instead, e.g. `256` with `u8`.
+/// Like with [tt_fits_type](macro.tt_fits_type.html), `usize` and `isize` are not supported.
+///
+/// If `max_depth` is given, groups nested deeper than it are compared by their string
+/// representations instead of by their contents, bounding the work done on deeply nested input.
+/// The two token trees being compared are at depth 1, so e.g. with `max_depth = [{ 1 }]`,
+/// `(a [b])` and `(a [ b ])` are equal, while `(a [b])` and `(a [b,])` are not, and with
+/// `max_depth = [{ 0 }]` the token trees themselves are compared by their string
+/// representations. No modes apply beyond the maximum depth.
+///
+/// If `self_type` is given, every `Self` identifier, including those nested in groups, is
+/// replaced by the given type before comparing, so e.g. with `self_type = [{ MyStruct }]`,
+/// `{fn new() -> Self}` and `{fn new() -> MyStruct}` are equal, as are `{Self::new()}` and
+/// `{MyStruct::new()}`. Only `Self` is replaced, not `self`.
 ///
 /// # Example
 ///
@@ -29,13 +96,13 @@ use std::iter::FromIterator;
 ///     } => {
 ///         tt_if!{
 ///             condition = [{tt_equal}]
-///	            input = [{ $id1 $id2 }]         // The two identifiers are here passed to 'tt_equal'
-///	            true = [{
+///             input = [{ $id1 $id2 }]         // The two identifiers are here passed to 'tt_equal'
+///             true = [{
 ///                 const $id1: bool = true;
-///	            }]
-///	            false = [{
+///             }]
+///             false = [{
 ///                 const $id1: bool = false;
-///	            }]
+///             }]
 ///         }
 ///     }
 /// }
@@ -50,6 +117,217 @@ use std::iter::FromIterator;
 ///
 /// ```
 ///
+/// # Modes
+///
+/// The optional `mode` key takes a list of modes that change how token trees are compared:
+///
+/// - `semantic`: Integer literals are compared by their value and type suffix instead of their
+///   spelling. E.g. `1000`, `1_000`, and `0x3E8` are equal, while `1u32` and `1` are not.
+///   Likewise for float literals, so e.g. `1e+3` and `1000.0` are equal, as are `1e-3` and
+///   `0.001`, or `1.` and `1.0`. Note that the lexer only produces a float literal with a
+///   trailing dot if no identifier or second dot follows it: `1.foo` is the tokens `1` `.`
+///   `foo`, and `1..2` is the range `1` `..` `2`.
+///   String literals are compared by the string they represent, with escapes
+///   resolved, so e.g. `"\x41"` and `"A"` are equal, as are `r"a\b"` and `"a\\b"`.
+///   Likewise for characters, bytes, and byte strings, so e.g. `'a'` and `'\x61'` are equal,
+///   as are `b'a'` and `b'\x61'`, or `b"a"`, `b"\x61"`, and `br#"a"#`, while a literal of one
+///   kind is never equal to a literal of another, e.g. `'a'` and `b'a'`.
+/// - `glob_matches_explicit`: A glob import `::*` matches any brace group following `::`,
+///   so e.g. `(use foo::*;)` and `(use foo::{a, b};)` are equal, while `(use foo::*;)` and
+///   `(use bar::{a};)` are not.
+///   This is a loose match: what the glob actually imports is not known to the macro,
+///   so the contents of the brace group are not checked.
+/// - `normalize_bounds_position`: The bounds of a function's generic parameters are compared
+///   regardless of whether they are given inline or in the where clause, and regardless of
+///   their order. E.g. `{fn f<T: Clone>() {}}` and `{fn f<T>() where T: Clone {}}` are equal,
+///   while `{fn f<T: Clone>() {}}` and `{fn f<T: Copy>() {}}` are not.
+/// - `concat_doc_attrs`: Consecutive `#[doc = "..."]` attributes, which is what `///` doc
+///   comments become, are compared as a single attribute with their values joined by newlines.
+///   E.g. two `///` lines are equal to a single `#[doc = ".."]` whose value contains both
+///   lines separated by `\n`.
+/// - `case_insensitive`: Identifiers are compared ignoring ASCII case, so e.g. `Foo` and `foo`
+///   are equal. Punctuation and literals are still compared exactly, so e.g. `"A"` and `"a"`
+///   are not equal.
+/// - `ignore_const_fn`: The `const` qualifier of functions is ignored, so e.g.
+///   `{const fn f() {}}` and `{fn f() {}}` are equal.
+///   Other uses of `const`, like `{const X: u8 = 0;}` or `{fn f<const N: usize>() {}}`,
+///   are still compared.
+/// - `normalize_extern_crate`: `extern crate` items are compared by the crate name only,
+///   ignoring any `as` rename, so e.g. `{extern crate foo;}` and `{extern crate foo as bar;}`
+///   are equal, while `{extern crate foo;}` and `{extern crate bar;}` are not.
+/// - `normalize_stringify`: string literals are compared as the code they contain, by parsing
+///   their values and comparing the resulting token trees in the same modes.
+///   E.g. `"{ (a, [b]) }"` and `"{(a,[b])}"` are equal, as are the outputs of `stringify!` on
+///   differently formatted code. If a value doesn't parse, the values are compared as they are.
+/// - `variant_fields_positional`: the fields of enum variants are compared positionally,
+///   ignoring the names of struct variants' fields, so e.g. `{enum E { Variant(u8, u16) }}` and
+///   `{enum E { Variant { a: u8, b: u16 } }}` are equal, while `{enum E { Variant(u16, u8) }}`
+///   is not equal to either.
+///   Only the variants in the bodies of `enum` items are affected, so e.g. struct items and
+///   struct literals like `{Point { x: 1 }}` are left as is.
+/// - `ignore_delimiters`: groups are compared by their contents only, so e.g. the macro calls
+///   `{println!("x")}`, `{println!["x"]}`, and `{println!{"x"}}` are equal.
+/// - `header_only`: only the headers of declarations are compared, i.e. everything up to
+///   the first brace group, `;`, or `=` that isn't within angle brackets, so e.g.
+///   `{fn f(x: u8) { x }}` and `{fn f(x: u8) { 0 }}` are equal, as are `{const X: u8 = 1;}`
+///   and `{const X: u8 = 2;}`. Only the compared token tree itself is truncated or, if it is
+///   a group, the group's contents, so e.g. `{fn f() -> [u8; 4] {}}` and
+///   `{fn f() -> [u8; 5] {}}` are not equal.
+/// - `strict`: token trees must also be of the same kind to be equal, e.g. an identifier is
+///   never equal to a group, even if the group's only token tree is an equal identifier.
+///   Such groups are e.g. what `macro_rules!` wraps `$x:expr` metavariables in, which are
+///   otherwise unwrapped before comparing if they have no delimiter.
+/// - `desugar_try`: the canonical desugaring of the `?` operator is compared as the `?`
+///   expression, so e.g. `{foo()?}` and `{match foo() { Ok(v) => v, Err(e) => return
+///   Err(e.into()) }}` are equal. Only this exact desugaring matches, except that the
+///   bindings may be named differently and the last arm may have a trailing comma; e.g.
+///   `Err(e) => return Err(From::from(e))` doesn't match.
+/// - `width_independent`: together with `semantic`, integer literals are compared regardless
+///   of the width of their type suffix, so e.g. `255u8`, `255u16`, and `0xFFusize` are equal.
+///   Whether the suffix is signed still matters, as does whether there is a suffix at all,
+///   so e.g. `255u8` is equal to neither `255i16` nor `255`. Without `semantic`, this mode has
+///   no effect, such that suffixes are significant by default.
+/// - `unrooted_attr_paths`: the paths of attributes are compared regardless of whether they
+///   start with `::`, so e.g. `{#[::serde::Serialize] struct S;}` and
+///   `{#[serde::Serialize] struct S;}` are equal. Only the attribute's own path is affected,
+///   not e.g. the paths in `#[derive(::serde::Serialize)]`.
+/// - `exact`: a strict baseline that ignores all other modes and compares token trees as in
+///   the `strict` mode, without trimming their string representations, and requiring
+///   punctuation to have the same spacing. E.g. `& &` and `&&` are never equal, and neither
+///   are a metavariable wrapped in an undelimited group and the token tree it holds.
+/// - `normalize_line_endings`: together with `semantic`, string literals are compared
+///   regardless of their line endings, with `\r\n` and `\r` taken to be `\n`. E.g.
+///   `"a\r\nb"`, `"a\rb"`, and `"a\nb"` are equal. Without `semantic`, this mode has no effect.
+/// - `ignore_suffixes`: the type suffixes of numeric literals are ignored, so e.g. `1u8`, `1i32`,
+///   and `1` are equal, as are `1.0f32` and `1.0`. An integer is still never equal to a float,
+///   e.g. `1` and `1.0` are not equal. Together with `semantic`, the values are compared,
+///   e.g. `0x10u8` and `16` are equal.
+/// - `assoc_bindings_unordered`: the associated type bindings of generic arguments are compared
+///   regardless of their order, so e.g. `{Iterator<Item = u8, IntoIter = I>}` and
+///   `{Iterator<IntoIter = I, Item = u8>}` are equal. Positional arguments are still compared
+///   in order, e.g. `{Foo<A, B, Item = u8>}` and `{Foo<B, A, Item = u8>}` are not equal.
+/// - `compare_impl_subject_only`: impl blocks are compared by their subject only, i.e.
+///   `impl Trait for Type` or `impl Type`, ignoring their generic parameters, where clauses,
+///   and bodies. So e.g. `{impl Foo for Bar { fn a() {} }}` and `{impl Foo for Bar {}}` are
+///   equal, while `{impl Foo for Bar {}}` and `{impl Foo for Baz {}}` are not.
+/// - `ignore_impl_generics_names`: the generic parameters of impl blocks are compared by their
+///   position instead of their names, so e.g. `{impl<T> Foo for Bar<T> { fn a(t: T) {} }}` and
+///   `{impl<U> Foo for Bar<U> { fn a(t: U) {} }}` are equal, while
+///   `{impl<T, U> Foo for Bar<T, U> {}}` and `{impl<T, U> Foo for Bar<U, T> {}}` are not.
+///   Names following a `::`, like the `T` of `Self::T`, and names redeclared by the generic
+///   parameters of a function or type alias in the impl block, within that item, are kept.
+///   It can also be given as `alpha_rename_generics`, as the parameters are consistently
+///   renamed to placeholders for their positions, such that alpha-equivalent impl blocks are
+///   equal. `applied` then names it `ignore_impl_generics_names`.
+/// - `ignore_loop_labels`: the labels of loops and blocks are compared regardless of their
+///   names, so e.g. `{'a: loop { break 'a; }}` and `{'b: loop { break 'b; }}` are equal.
+///   Any two labels are then equal, so e.g. which of two nested loops a `break` exits is not
+///   compared. Other lifetimes, like those of `&'a str`, are still compared.
+/// - `strip_ident_numeric_suffix`: identifiers are compared without any trailing digits, so
+///   e.g. `tmp1`, `tmp42`, and `tmp` are equal, while `foo1` and `bar1` are not. Since
+///   identifiers can't start with a digit, the rest of an identifier is never empty.
+/// - `raw_insensitive`: identifiers are compared without any raw identifier prefix `r#`, so
+///   e.g. `r#foo` and `foo` are equal, as are the raw identifier `r#type` and the keyword
+///   `type`. By default, they are distinct, as a keyword and an identifier spelling the same
+///   word are not interchangeable.
+/// - `ignore_use_rename`: `use` items are compared ignoring any `as` renames, so e.g.
+///   `{pub use foo::Bar as Baz;}` and `{pub use foo::Bar;}` are equal, as are
+///   `{use foo::{a as b, c};}` and `{use foo::{a, c};}`, while `{use foo::Bar as Baz;}` and
+///   `{use bar::Bar as Baz;}` are not. Other uses of `as`, e.g. in `<T as Trait>::f()`, are
+///   still compared.
+/// - `signed_numbers`: a `-` or `+` followed by a number is taken to be a single token tree
+///   when splitting the input into the token trees to compare, so e.g. `-1 -1` and `-1 - 1`
+///   can be compared, and are equal. Without it, `-1` is the two token trees `-` and `1`,
+///   such that e.g. `- 1` is compared as a minus and a one.
+///   Together with `semantic`, a unary `+` before a number is ignored and signed numbers are
+///   compared by their values, so e.g. `+5` and `5` are equal, as are `-5` and `-0x5`, while
+///   `-5` and `+5` are not. A `+` is unary if it starts a group or follows other punctuation,
+///   so e.g. `{a + 5}` and `{a 5}` are not equal.
+/// - `ignore_type_ascription`: type ascriptions are ignored, i.e. everything from a `:` up to
+///   the next `=`, `,`, or `;`, so e.g. `{const X: i32 = 5;}` and `{const X = 5;}` are equal,
+///   while `{const X: i32 = 5;}` and `{const Y: i32 = 5;}` are not. This also applies to e.g.
+///   function parameters and struct fields, but not to `::` or to the `:` following a lifetime.
+/// - `str_ignore_case`: together with `semantic`, string literals are compared ignoring ASCII
+///   case after their escapes are resolved, so e.g. `"Hello"`, `"hello"`, and `"\x48ELLO"` are
+///   equal, while `"Hello"` and `"Help"` are not. Other literals are compared as in `semantic`.
+///   Without `semantic`, this mode has no effect.
+/// - `elide_static_str`: the `'static` lifetime of `&'static str` is ignored, so e.g.
+///   `{fn f() -> &'static str}` and `{fn f() -> &str}` are equal. Only references to `str`
+///   are affected, e.g. `{&'static [u8]}` and `{&[u8]}` are not equal, and neither are
+///   `{&'static str}` and `{&String}`.
+/// - `ignore_struct_update`: the struct updates of struct literals are ignored, so e.g.
+///   `{Foo { a: 1, ..base }}` and `{Foo { a: 1 }}` are equal, while `{Foo { a: 1, ..base }}`
+///   and `{Foo { a: 2, ..base }}` are not. A struct literal is taken to be any identifier
+///   followed by a brace group, and the rest pattern `..` of e.g. `{Foo { a, .. }}` is still
+///   compared.
+/// - `normalize_field_shorthand`: the fields of struct literals are compared regardless of
+///   whether they use the field shorthand, so e.g. `{Foo { a, b }}` and `{Foo { a: a, b }}` are
+///   equal, while `{Foo { a }}` and `{Foo { a: b }}` are not. A struct literal is taken to be
+///   any identifier followed by a brace group, so e.g. struct patterns are also affected.
+/// - `ignore_param_mut`: the `mut` of function parameters is ignored, so e.g.
+///   `{fn f(mut x: i32) {}}` and `{fn f(x: i32) {}}` are equal, while `{fn f(mut x: i32) {}}`
+///   and `{fn f(x: u32) {}}` are not. Only a `mut` starting a parameter is ignored, e.g.
+///   `{fn f(&mut self) {}}` and `{fn f(&self) {}}` are not equal.
+/// - `anon_lifetime_wildcard`: the anonymous lifetime `'_` matches any lifetime, so e.g.
+///   `{Foo<'_>}` and `{Foo<'a>}` are equal, as are `{Foo<'_>}` and `{Foo<'_>}`, while named
+///   lifetimes must still be equal, e.g. `{Foo<'a>}` and `{Foo<'b>}` are not. A lifetime is
+///   never equal to something else, e.g. `{Foo<'_>}` and `{Foo<T>}` are not equal.
+/// - `ignore_let_mut`: the `mut` of `let` bindings is ignored, so e.g. `{let mut x = 1;}` and
+///   `{let x = 1;}` are equal, while `{let mut x = 1;}` and `{let mut y = 1;}` are not. Only a
+///   `mut` directly following `let` is ignored, e.g. `{let x = &mut y;}` and `{let x = &y;}` are
+///   not equal.
+/// - `ignore_result_err`: the error type of `Result<T, E>` is ignored, so e.g.
+///   `{fn f() -> Result<u8, X>}` and `{fn f() -> Result<u8, Y>}` are equal, while
+///   `{Result<u8, X>}` and `{Result<u16, X>}` are not. This only applies to the `Result`
+///   identifier followed by exactly two generic arguments, so e.g. `{io::Result<u8>}` and
+///   `{Result<u8, X>}` are not equal, and neither are `{MyResult<u8, X>}` and
+///   `{MyResult<u8, Y>}`.
+/// - `ignore_unsafe_blocks`: `unsafe` blocks are compared as their contents, so e.g.
+///   `{let x = unsafe { foo() };}` and `{let x = foo();}` are equal, while
+///   `{unsafe { foo() }}` and `{bar()}` are not. Only an `unsafe` directly followed by a brace
+///   group is a block, so e.g. `{unsafe fn f() {}}` and `{fn f() {}}` are not equal.
+/// - `unit_struct_forms`: unit structs are compared regardless of whether their body is `;` or
+///   `{}`, so e.g. `{struct Foo;}` and `{struct Foo {}}` are equal, while `{struct Foo;}` and
+///   `{struct Foo { x: u8 }}` are not. Only the body directly following the struct's name and
+///   generic parameters is affected.
+/// - `ignore_static_bounds`: the `'static` bounds of generic parameters are ignored, so e.g.
+///   `{fn f<T: 'static>()}` and `{fn f<T>()}` are equal, as are `{fn f<T: Clone + 'static>()}`
+///   and `{fn f<T: Clone>()}`. Only bounds that are exactly `'static` are ignored, e.g.
+///   `{fn f<T: 'a>()}` and `{fn f<T>()}` are not equal.
+///
+/// Since `tt_if` only passes the `input` key to its condition, `tt_call` must be used to
+/// give a mode.
+///
+/// Example:
+///
+/// ```
+/// use tt_equal::tt_equal;
+/// use tt_call::tt_call;
+///
+/// macro_rules! bool_const{
+///     {
+///         name = [{ $name:ident }]
+///         is_equal = [{ $is_equal:tt }]
+///     } => {
+///         const $name: bool = $is_equal;
+///     }
+/// }
+///
+/// tt_call!{
+///     macro = [{ tt_equal }]
+///     input = [{ 1_000 0x3E8 }]
+///     mode = [{ semantic }]
+///     ~~> bool_const!{
+///         name = [{ SHOULD_BE_TRUE }]
+///     }
+/// }
+///
+/// fn main() {
+///     assert_eq!(SHOULD_BE_TRUE, true);
+/// }
+///
+/// ```
+///
 /// # Caveat
 ///
 /// This is a procedural macro and therefore has corresponding restrictions on where it can be used.
@@ -68,25 +346,25 @@ use std::iter::FromIterator;
 /// use tt_call::tt_if;
 ///
 /// tt_if!{
-///	    condition = [{tt_equal}]
-///	    input = [{ (Two tokens) (Two tokens) }]
-///	    true = [{
-///		    const SHOULD_BE_TRUE: bool = true;
-///	    }]
-///	    false = [{
-///		    const SHOULD_BE_TRUE: bool = false;
-///	    }]
+///     condition = [{tt_equal}]
+///     input = [{ (Two tokens) (Two tokens) }]
+///     true = [{
+///         const SHOULD_BE_TRUE: bool = true;
+///     }]
+///     false = [{
+///         const SHOULD_BE_TRUE: bool = false;
+///     }]
 /// }
 ///
 /// tt_if!{
-///	    condition = [{tt_equal}]
-///	    input = [{ (Two tokens) (Three tokens here) }]
-///	    true = [{
-///		    const SHOULD_BE_FALSE: bool = true;
-///	    }]
-///	    false = [{
-///		    const SHOULD_BE_FALSE: bool = false;
-///	    }]
+///     condition = [{tt_equal}]
+///     input = [{ (Two tokens) (Three tokens here) }]
+///     true = [{
+///         const SHOULD_BE_FALSE: bool = true;
+///     }]
+///     false = [{
+///         const SHOULD_BE_FALSE: bool = false;
+///     }]
 /// }
 ///
 /// fn main() {
@@ -96,168 +374,2279 @@ use std::iter::FromIterator;
 ///
 /// ```
 #[proc_macro]
-pub fn tt_equal(item: TokenStream) -> TokenStream {
-    let (caller, lhs, rhs) = validate(item);
+pub fn tt_equal(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_tt_equal(item.into()).into()
+}
+
+///
+/// Expands 'tt_equal', returning a `compile_error!` invocation if its input is malformed.
+///
+fn expand_tt_equal(item: TokenStream) -> TokenStream {
+    let (caller, lhs, rhs, mut mode, max_tokens, mut extra_values) = match validate(
+        "tt_equal",
+        item,
+        &[
+            "emit_applied",
+            "echo",
+            "expected_type",
+            "max_depth",
+            "self_type",
+        ],
+    ) {
+        Ok(validated) => validated,
+        Err(error) => return error.into_compile_error(),
+    };
+    if let Err(error) = check_max_tokens("tt_equal", &[&lhs, &rhs], max_tokens) {
+        return error.into_compile_error();
+    }
+    let mut flags = Vec::new();
+    for key in &["emit_applied", "echo"] {
+        flags.push(match extra_values.remove(0) {
+            Some(value) => match parse_bool("tt_equal", key, value) {
+                Ok(flag) => flag,
+                Err(error) => return error.into_compile_error(),
+            },
+            None => false,
+        });
+    }
+    let (emit_applied, echo) = (flags[0], flags[1]);
+    let echoed = lhs.clone();
+    let (lhs, rhs) = match extra_values.remove(0) {
+        Some(expected_type) => {
+            let typed = parse_integer_type("tt_equal", expected_type).and_then(|range| {
+                Ok((
+                    integer_in_range("tt_equal", &lhs, range)?,
+                    integer_in_range("tt_equal", &rhs, range)?,
+                ))
+            });
+            match typed {
+                Ok(typed) => typed,
+                Err(error) => return error.into_compile_error(),
+            }
+        }
+        None => (lhs, rhs),
+    };
+    if let Some(max_depth) = extra_values.remove(0) {
+        match parse_usize("tt_equal", "max_depth", max_depth) {
+            Ok(max_depth) => mode.max_depth = Some(max_depth),
+            Err(error) => return error.into_compile_error(),
+        }
+    }
+    let (lhs, rhs) = match extra_values.remove(0) {
+        Some(self_type) if self_type.is_empty() => {
+            return Error::new(
+                Span::call_site(),
+                "'tt_equal' expects 'self_type' to be a type but received nothing.".to_string(),
+            )
+            .into_compile_error()
+        }
+        Some(self_type) => (
+            substitute_self(lhs, &self_type),
+            substitute_self(rhs, &self_type),
+        ),
+        None => (lhs, rhs),
+    };
+
+    let is_equal = tokens_equal_in_mode(&lhs, &rhs, &mode);
+    if !emit_applied && !echo {
+        return return_to_tt(caller, "is_equal", is_equal);
+    }
+    let mut values = vec![("is_equal", is_equal.to_string().parse().unwrap())];
+    if emit_applied {
+        let applied = MODES.iter().filter(|name| {
+            let mut without = mode.clone();
+            let flag = mode_flag(&mut without, name).unwrap();
+            *flag && {
+                *flag = false;
+                tokens_equal_in_mode(&lhs, &rhs, &without) != is_equal
+            }
+        });
+        let applied = TokenStream::from_iter(
+            applied.map(|name| TokenTree::from(Ident::new(name, Span::call_site()))),
+        );
+        values.push(("applied", applied));
+    }
+    if echo {
+        let canonical = if is_equal { echoed } else { TokenStream::new() };
+        values.push(("canonical", canonical));
+    }
+    return_values_to_tt(caller, values)
+}
+
+///
+/// A predicate for whether two token trees are not equal.
+/// <sup>**[[tt-call](https://docs.rs/tt-call/)]**</sup>
+///
+/// The inverse of [`tt_equal`](macro.tt_equal.html): given two token trees, it compares them
+/// exactly like `tt_equal` and returns whether they are not equal.
+/// Intended for use with [tt_if](https://docs.rs/tt-call/1.0.6/tt_call/macro.tt_if.html),
+/// where it avoids having to swap the `true` and `false` branches of a `tt_equal` condition.
+///
+/// # Input
+///
+/// - `input = [{` exactly two token trees `}]`
+/// - `mode = [{` optionally, the modes to compare in
+///   (see [`tt_equal`'s modes](macro.tt_equal.html#modes)) `}]`
+/// - `defaults = [{` optionally, more modes to compare in
+///   (see [`tt_equal`'s input](macro.tt_equal.html#input)) `}]`
+/// - `max_tokens = [{` optionally, the maximum number of tokens the input may contain
+///   (see [`tt_equal`'s input](macro.tt_equal.html#input)) `}]`
+///
+/// # Output
+///
+/// - `is_not_equal = [{` either true or false `}]`
+///
+/// # Example
+///
+/// ```
+/// use tt_equal::tt_not_equal;
+/// use tt_call::tt_if;
+///
+/// macro_rules! different_ident{
+///     {
+///         $id1:ident, $id2:ident
+///     } => {
+///         tt_if!{
+///             condition = [{tt_not_equal}]
+///             input = [{ $id1 $id2 }]
+///             true = [{
+///                 const $id1: bool = true;
+///             }]
+///             false = [{
+///                 const $id1: bool = false;
+///             }]
+///         }
+///     }
+/// }
+///
+/// different_ident!(AN_IDENT, AN_IDENT);            // Equal identifiers result in a false constant
+/// different_ident!(A_DIFFERENT_IDENT, AN_IDENT);   // Different identifiers result in a true constant
+///
+/// fn main() {
+///     assert_eq!(AN_IDENT, false);
+///     assert_eq!(A_DIFFERENT_IDENT, true);
+/// }
+///
+/// ```
+#[proc_macro]
+pub fn tt_not_equal(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_tt_not_equal(item.into()).into()
+}
 
-    assert!(lhs.len() > 0);
-    assert!(rhs.len() > 0);
+///
+/// Expands 'tt_not_equal', returning a `compile_error!` invocation if its input is malformed.
+///
+fn expand_tt_not_equal(item: TokenStream) -> TokenStream {
+    let (caller, lhs, rhs, mode, max_tokens, _) = match validate("tt_not_equal", item, &[]) {
+        Ok(validated) => validated,
+        Err(error) => return error.into_compile_error(),
+    };
+    if let Err(error) = check_max_tokens("tt_not_equal", &[&lhs, &rhs], max_tokens) {
+        return error.into_compile_error();
+    }
 
     return_to_tt(
         caller,
-        if lhs.len() == rhs.len() {
-            lhs.into_iter()
-                .zip(rhs.into_iter())
-                .all(|(lhs, rhs)| lhs.to_string().trim() == rhs.to_string().trim())
-        } else {
-            false
-        },
+        "is_not_equal",
+        !tokens_equal_in_mode(&lhs, &rhs, &mode),
     )
 }
 
 ///
-/// Validates that the input to 'tt_equal' is correct and returns:
-/// 0. The callers opaque tt bundle
-/// 1. The left-hand side of the input to compare
-/// 2. The right-hand side of the input to compare
+/// A predicate for whether any number of token trees are all equal.
+/// <sup>**[[tt-call](https://docs.rs/tt-call/)]**</sup>
 ///
-fn validate(item: TokenStream) -> (TokenTree, Vec<TokenTree>, Vec<TokenTree>) {
-    let mut iter = item.into_iter();
+/// Given one or more token trees, it compares each of them to the first exactly like
+/// [`tt_equal`](macro.tt_equal.html) and returns whether they are all equal.
+/// A single token tree is trivially equal to itself.
+/// Intended for use with [tt_if](https://docs.rs/tt-call/1.0.6/tt_call/macro.tt_if.html).
+///
+/// # Input
+///
+/// - `input = [{` one or more token trees `}]`
+/// - `mode = [{` optionally, the modes to compare in
+///   (see [`tt_equal`'s modes](macro.tt_equal.html#modes)) `}]`
+/// - `defaults = [{` optionally, more modes to compare in
+///   (see [`tt_equal`'s input](macro.tt_equal.html#input)) `}]`
+/// - `max_tokens = [{` optionally, the maximum number of tokens the input may contain
+///   (see [`tt_equal`'s input](macro.tt_equal.html#input)) `}]`
+///
+/// # Output
+///
+/// - `is_equal = [{` either true or false `}]`
+///
+/// # Example
+///
+/// ```
+/// use tt_equal::tt_all_equal;
+/// use tt_call::tt_if;
+///
+/// tt_if!{
+///     condition = [{tt_all_equal}]
+///     input = [{ a a a a a }]
+///     true = [{
+///         const SHOULD_BE_TRUE: bool = true;
+///     }]
+///     false = [{
+///         const SHOULD_BE_TRUE: bool = false;
+///     }]
+/// }
+///
+/// tt_if!{
+///     condition = [{tt_all_equal}]
+///     input = [{ a a a a b }]
+///     true = [{
+///         const SHOULD_BE_FALSE: bool = true;
+///     }]
+///     false = [{
+///         const SHOULD_BE_FALSE: bool = false;
+///     }]
+/// }
+///
+/// fn main() {
+///     assert_eq!(SHOULD_BE_TRUE, true);
+///     assert_eq!(SHOULD_BE_FALSE, false);
+/// }
+///
+/// ```
+#[proc_macro]
+pub fn tt_all_equal(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_tt_all_equal(item.into()).into()
+}
 
-    let caller = iter
-        .next()
-        .expect("'tt_equal' did not receive caller's tt bundle.");
-    let key = iter
-        .next()
-        .expect("'tt_equal' expects a key-value pair as input, but did not receive a key.");
-    if key.to_string().trim() != "input".to_string() {
-        panic!(
-            "'tt_equal' expects its input's key to be named 'input' but it was '{}'",
-            key.to_string().trim()
+///
+/// Expands 'tt_all_equal', returning a `compile_error!` invocation if its input is malformed.
+///
+fn expand_tt_all_equal(item: TokenStream) -> TokenStream {
+    let (caller, sides, mode, max_tokens, _) = match validate_sides("tt_all_equal", item, &[]) {
+        Ok(validated) => validated,
+        Err(error) => return error.into_compile_error(),
+    };
+    if sides.is_empty() {
+        return Error::new(
+            Span::call_site(),
+            "'tt_all_equal' expects at least one token tree to compare but received none."
+                .to_string(),
         )
+        .into_compile_error();
     }
-    let separator = iter
-        .next()
-        .expect("'tt_equal' expects a key value pair as input but did not receive it.")
-        .to_string();
-    if separator != "=".to_string() {
-        panic!(
-            "'tt_equal' expects its input key-value pairs to be separated by a '=' \
-             but instead received '{}'",
-            separator
-        );
-    }
-    let value_group = iter
-        .next()
-        .expect("'tt_equal' expects a key-value pair as input but received no value.");
-    if iter.next().is_some() {
-        panic!("'tt_equal' expects only a key-value pair as input but received more.")
-    }
-    let mut unbracketed_group = expect_group(value_group, Delimiter::Bracket).into_iter();
-    let braced_group = unbracketed_group.next().expect(
-        "'tt_equal' expects its input value to be within '[{..}]' \
-         but the '{..}' was not given.",
-    );
-    if unbracketed_group.next().is_some() {
-        panic!(
-            "'tt_equal' expects its input value to be within '[{..}]' \
-             but it received additional tokens after the braces ('{..}')."
-        )
+    if let Err(error) = check_max_tokens("tt_all_equal", &Vec::from_iter(sides.iter()), max_tokens)
+    {
+        return error.into_compile_error();
     }
-    let mut clean_value = expect_group(braced_group, Delimiter::Brace).into_iter();
-    let lhs = get_next_joint_token(&mut clean_value)
-        .expect("'tt_equal' expects two token tree to compare but received none.");
 
-    let rhs = get_next_joint_token(&mut clean_value)
-        .expect("'tt_equal' expects two token tree to compare but received only one");
-    if let Some(x) = clean_value.next() {
-        panic!(
-            "'tt_equal' expects two token tree to compare but received more: '{:?} {:?} {:?}'",
-            lhs, rhs, x
-        )
-    }
-    (caller, lhs, rhs)
+    return_to_tt(
+        caller,
+        "is_equal",
+        sides[1..]
+            .iter()
+            .all(|side| tokens_equal_in_mode(&sides[0], side, &mode)),
+    )
 }
 
 ///
-/// Unwraps a token tree, assuming it has the given delimiter, and returns
-/// its contents
+/// A predicate for whether a token tree equals any of a set of alternatives.
+/// <sup>**[[tt-call](https://docs.rs/tt-call/)]**</sup>
 ///
-fn expect_group(tt: TokenTree, expected_delimiter: Delimiter) -> TokenStream {
-    if let TokenTree::Group(g) = tt {
-        if expected_delimiter == g.delimiter() {
-            g.stream()
-        } else {
-            panic!(
-                "'tt_equal' expects delimiter '{:?}' but got '{:?}'.",
-                expected_delimiter,
-                g.delimiter()
-            );
-        }
-    } else {
-        panic!(
-            "'tt_equal' expects a group of tokens inside {:?} but got '{:?}'",
-            expected_delimiter, tt
-        );
+/// Given a token tree and a group of alternatives, it compares the token tree to each token
+/// tree in the group exactly like [`tt_equal`](macro.tt_equal.html) and returns whether any
+/// of them are equal.
+/// Intended for use with [tt_if](https://docs.rs/tt-call/1.0.6/tt_call/macro.tt_if.html).
+///
+/// # Input
+///
+/// - `input = [{` a token tree followed by a group containing the alternatives `}]`
+/// - `mode = [{` optionally, the modes to compare in
+///   (see [`tt_equal`'s modes](macro.tt_equal.html#modes)) `}]`
+/// - `defaults = [{` optionally, more modes to compare in
+///   (see [`tt_equal`'s input](macro.tt_equal.html#input)) `}]`
+/// - `max_tokens = [{` optionally, the maximum number of tokens the input may contain
+///   (see [`tt_equal`'s input](macro.tt_equal.html#input)) `}]`
+///
+/// # Output
+///
+/// - `is_equal = [{` either true or false `}]`
+///
+/// # Example
+///
+/// ```
+/// use tt_equal::tt_equal_any;
+/// use tt_call::tt_if;
+///
+/// macro_rules! is_keyword{
+///     {
+///         $id:ident
+///     } => {
+///         tt_if!{
+///             condition = [{tt_equal_any}]
+///             input = [{ $id (fn struct enum) }]
+///             true = [{
+///                 const IS_KEYWORD: bool = true;
+///             }]
+///             false = [{
+///                 const IS_KEYWORD: bool = false;
+///             }]
+///         }
+///     }
+/// }
+///
+/// is_keyword!(struct);
+///
+/// fn main() {
+///     assert_eq!(IS_KEYWORD, true);
+/// }
+///
+/// ```
+#[proc_macro]
+pub fn tt_equal_any(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_tt_equal_any(item.into()).into()
+}
+
+///
+/// Expands 'tt_equal_any', returning a `compile_error!` invocation if its input is malformed.
+///
+fn expand_tt_equal_any(item: TokenStream) -> TokenStream {
+    let (caller, needle, alternatives, mode, max_tokens, _) =
+        match validate("tt_equal_any", item, &[]) {
+            Ok(validated) => validated,
+            Err(error) => return error.into_compile_error(),
+        };
+    if let Err(error) = check_max_tokens("tt_equal_any", &[&needle, &alternatives], max_tokens) {
+        return error.into_compile_error();
     }
+    let alternatives = match joint_tokens_in_group("tt_equal_any", alternatives) {
+        Ok(alternatives) => alternatives,
+        Err(error) => return error.into_compile_error(),
+    };
+
+    let is_equal = alternatives.into_iter().any(|alternative| {
+        tokens_equal_in_mode(&needle, &TokenStream::from_iter(alternative), &mode)
+    });
+    return_to_tt(caller, "is_equal", is_equal)
 }
 
 ///
-/// Constructs the result of 'tt_equal'
+/// Finds where the contents of two groups first differ.
+/// <sup>**[[tt-call](https://docs.rs/tt-call/)]**</sup>
 ///
-fn return_to_tt(caller: TokenTree, b: bool) -> TokenStream {
-    let return_call: TokenStream = "tt_call::tt_return!".parse().expect(
-        "'tt_equal' internal error 1. Please file a bug with the tt-equal crate maintainers.",
-    );
-    let return_value: TokenStream = format!("is_equal = [ {{ {} }} ]", b).parse().expect(
-        "'tt_equal' internal error 2.  Please file a bug with the tt-equal crate maintainers.",
-    );
+/// Given two groups, compares their contents token tree by token tree, exactly like
+/// [`tt_equal`](macro.tt_equal.html), and returns the zero-based index of the first pair
+/// that isn't equal.
+/// If one group's contents are a prefix of the other's, the index is that of the first
+/// token tree the shorter group lacks.
+/// Useful for debugging generated macros, where a plain true/false doesn't tell where
+/// the output went wrong.
+///
+/// # Input
+///
+/// - `input = [{` two groups to compare `}]`
+/// - `mode = [{` optionally, the modes to compare in
+///   (see [`tt_equal`'s modes](macro.tt_equal.html#modes)) `}]`
+/// - `defaults = [{` optionally, more modes to compare in
+///   (see [`tt_equal`'s input](macro.tt_equal.html#input)) `}]`
+/// - `max_tokens = [{` optionally, the maximum number of tokens the input may contain
+///   (see [`tt_equal`'s input](macro.tt_equal.html#input)) `}]`
+///
+/// # Output
+///
+/// - `diff_index = [{` the index of the first differing token tree, or `none` if the groups'
+///   contents are equal `}]`
+///
+/// # Example
+///
+/// ```
+/// use tt_equal::tt_first_diff;
+/// use tt_call::tt_call;
+///
+/// macro_rules! first_diff_const{
+///     {
+///         diff_index = [{ none }]
+///     } => {
+///         const FIRST_DIFF: Option<usize> = None;
+///     };
+///     {
+///         diff_index = [{ $index:literal }]
+///     } => {
+///         const FIRST_DIFF: Option<usize> = Some($index);
+///     };
+/// }
+///
+/// tt_call!{
+///     macro = [{ tt_first_diff }]
+///     input = [{ (a b c) (a x c) }]
+///     ~~> first_diff_const
+/// }
+///
+/// fn main() {
+///     assert_eq!(FIRST_DIFF, Some(1));
+/// }
+///
+/// ```
+#[proc_macro]
+pub fn tt_first_diff(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_tt_first_diff(item.into()).into()
+}
 
-    let mut return_body: Vec<_> = Vec::new();
-    return_body.push(caller);
-    return_body.extend(return_value);
-    let return_call_argument = TokenTree::from(Group::new(
-        Delimiter::Brace,
-        TokenStream::from_iter(return_body.into_iter()),
-    ));
+///
+/// Expands 'tt_first_diff', returning a `compile_error!` invocation if its input is malformed.
+///
+fn expand_tt_first_diff(item: TokenStream) -> TokenStream {
+    let (caller, lhs, rhs, mode, max_tokens, _) = match validate("tt_first_diff", item, &[]) {
+        Ok(validated) => validated,
+        Err(error) => return error.into_compile_error(),
+    };
+    if let Err(error) = check_max_tokens("tt_first_diff", &[&lhs, &rhs], max_tokens) {
+        return error.into_compile_error();
+    }
+    let (lhs, rhs) = match (
+        joint_tokens_in_group("tt_first_diff", lhs),
+        joint_tokens_in_group("tt_first_diff", rhs),
+    ) {
+        (Ok(lhs), Ok(rhs)) => (lhs, rhs),
+        (Err(error), _) | (_, Err(error)) => return error.into_compile_error(),
+    };
 
-    let mut result: Vec<TokenTree> = Vec::new();
-    result.extend(return_call);
-    result.push(return_call_argument);
+    let first_diff = (0..lhs.len().max(rhs.len())).find(|&i| match (lhs.get(i), rhs.get(i)) {
+        (Some(lhs), Some(rhs)) => !tokens_equal_in_mode(
+            &TokenStream::from_iter(lhs.clone()),
+            &TokenStream::from_iter(rhs.clone()),
+            &mode,
+        ),
+        _ => true,
+    });
+    let diff_index = match first_diff {
+        Some(index) => TokenTree::from(Literal::usize_unsuffixed(index)),
+        None => TokenTree::from(Ident::new("none", Span::call_site())),
+    };
+    return_values_to_tt(caller, vec![("diff_index", TokenStream::from(diff_index))])
+}
+
+///
+/// A predicate for whether a sequence of token trees appears within another.
+/// <sup>**[[tt-call](https://docs.rs/tt-call/)]**</sup>
+///
+/// Given two groups, a haystack and a needle, returns whether the needle's contents occur
+/// consecutively somewhere in the haystack's contents.
+/// The token trees are compared exactly like [`tt_equal`](macro.tt_equal.html),
+/// so e.g. the needle `(=)` isn't found in the haystack `(a += b)`.
+/// An empty needle is found in any haystack.
+///
+/// # Input
+///
+/// - `input = [{` the haystack group followed by the needle group `}]`
+/// - `mode = [{` optionally, the modes to compare in
+///   (see [`tt_equal`'s modes](macro.tt_equal.html#modes)) `}]`
+/// - `defaults = [{` optionally, more modes to compare in
+///   (see [`tt_equal`'s input](macro.tt_equal.html#input)) `}]`
+/// - `max_tokens = [{` optionally, the maximum number of tokens the input may contain
+///   (see [`tt_equal`'s input](macro.tt_equal.html#input)) `}]`
+///
+/// # Output
+///
+/// - `contains = [{` either true or false `}]`
+///
+/// # Example
+///
+/// ```
+/// use tt_equal::tt_contains;
+/// use tt_call::tt_if;
+///
+/// macro_rules! uses_unsafe{
+///     {
+///         $body:tt
+///     } => {
+///         tt_if!{
+///             condition = [{tt_contains}]
+///             input = [{ $body (unsafe) }]
+///             true = [{
+///                 const USES_UNSAFE: bool = true;
+///             }]
+///             false = [{
+///                 const USES_UNSAFE: bool = false;
+///             }]
+///         }
+///     }
+/// }
+///
+/// uses_unsafe!({ let x = unsafe { f() }; });
+///
+/// fn main() {
+///     assert_eq!(USES_UNSAFE, true);
+/// }
+///
+/// ```
+#[proc_macro]
+pub fn tt_contains(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_tt_contains(item.into()).into()
+}
+
+///
+/// Expands 'tt_contains', returning a `compile_error!` invocation if its input is malformed.
+///
+fn expand_tt_contains(item: TokenStream) -> TokenStream {
+    let (caller, haystack, needle, mode, max_tokens, _) = match validate("tt_contains", item, &[]) {
+        Ok(validated) => validated,
+        Err(error) => return error.into_compile_error(),
+    };
+    if let Err(error) = check_max_tokens("tt_contains", &[&haystack, &needle], max_tokens) {
+        return error.into_compile_error();
+    }
+    let (haystack, needle) = match (
+        joint_tokens_in_group("tt_contains", haystack),
+        joint_tokens_in_group("tt_contains", needle),
+    ) {
+        (Ok(haystack), Ok(needle)) => (haystack, needle),
+        (Err(error), _) | (_, Err(error)) => return error.into_compile_error(),
+    };
 
-    return TokenStream::from_iter(result.into_iter());
+    let needle_len = needle.len();
+    let needle = TokenStream::from_iter(needle.concat());
+    let contains = needle_len == 0
+        || haystack.windows(needle_len).any(|window| {
+            tokens_equal_in_mode(&TokenStream::from_iter(window.concat()), &needle, &mode)
+        });
+    return_to_tt(caller, "contains", contains)
 }
 
 ///
-/// Tries to get the next token from the token stream iterator.
+/// A predicate for whether an integer's byte representation equals a byte array.
+/// <sup>**[[tt-call](https://docs.rs/tt-call/)]**</sup>
 ///
-/// If no token is available, `None` is returned.
+/// Given an integer literal and a bracketed array of byte literals, returns whether the
+/// array holds the bytes of the integer in the given endianness.
+/// The width of the integer is taken to be the length of the array, so an integer that does not
+/// fit in that many bytes is never equal to the array.
+/// Useful for validating `const` byte tables against their intended values at macro time.
 ///
-/// If the token is a multi-character punctuation, all the token in the punctuation are turned.
-/// I.e:
-///   * '+' will be returned as `Vec['+']`.
-///   * `+=` will be returned as `Vec['+', '=']`.
-///   * `..=` will be returned as `Vec['.', '.', '=']`.
+/// # Input
 ///
-/// For non-punctuation tokens, the vec will always contain 1 token.
+/// - `input = [{` an unsigned integer literal followed by an array of comma-separated
+///   byte literals `}]`
+/// - `endian = [{` either `le` or `be` `}]`
 ///
-fn get_next_joint_token(stream: &mut IntoIter) -> Option<Vec<TokenTree>> {
-    let first = stream.next()?;
-    if let TokenTree::Punct(last) = first {
-        let mut tokens = vec![last];
-        while let Spacing::Joint = tokens.last().unwrap().spacing() {
-            let next = stream.next().unwrap();
-            if let TokenTree::Punct(p) = next {
-                tokens.push(p);
-            } else {
-                panic!(
-                    "'tt_equal' encountered a Punct token joint with \
-                     a non-Punct token: '{:?} {:?}'",
-                    tokens, next
-                );
-            }
-        }
-        Some(tokens.into_iter().map(|p| TokenTree::Punct(p)).collect())
-    } else {
-        Some(vec![first])
+/// # Output
+///
+/// - `is_equal = [{` either true or false `}]`
+///
+/// # Example
+///
+/// ```
+/// use tt_equal::tt_bytes_equal_int;
+/// use tt_call::tt_call;
+///
+/// macro_rules! bool_const{
+///     {
+///         name = [{ $name:ident }]
+///         is_equal = [{ $is_equal:tt }]
+///     } => {
+///         const $name: bool = $is_equal;
+///     }
+/// }
+///
+/// tt_call!{
+///     macro = [{ tt_bytes_equal_int }]
+///     input = [{ 0x12345678 [0x78, 0x56, 0x34, 0x12] }]
+///     endian = [{ le }]
+///     ~~> bool_const!{
+///         name = [{ IS_LITTLE_ENDIAN }]
+///     }
+/// }
+///
+/// tt_call!{
+///     macro = [{ tt_bytes_equal_int }]
+///     input = [{ 0x12345678 [0x78, 0x56, 0x34, 0x12] }]
+///     endian = [{ be }]
+///     ~~> bool_const!{
+///         name = [{ IS_BIG_ENDIAN }]
+///     }
+/// }
+///
+/// fn main() {
+///     assert_eq!(IS_LITTLE_ENDIAN, true);
+///     assert_eq!(IS_BIG_ENDIAN, false);
+/// }
+///
+/// ```
+#[proc_macro]
+pub fn tt_bytes_equal_int(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_tt_bytes_equal_int(item.into())
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+///
+/// Expands 'tt_bytes_equal_int', returning an error if its input is malformed.
+///
+fn expand_tt_bytes_equal_int(item: TokenStream) -> Result<TokenStream, Error> {
+    let (caller, mut values, _) =
+        validate_key_values("tt_bytes_equal_int", item, &["input", "endian"], &[])?;
+    let endian = values.pop().unwrap();
+    let little_endian = match endian.to_string().trim() {
+        "le" => true,
+        "be" => false,
+        endian_string => {
+            return Err(Error::new(
+                endian
+                    .into_iter()
+                    .next()
+                    .map_or_else(Span::call_site, |tt| tt.span()),
+                format!(
+                    "'tt_bytes_equal_int' expects its endianness to be either 'le' or 'be' \
+                     but received '{}'",
+                    endian_string
+                ),
+            ))
+        }
+    };
+    let mut input = values.pop().unwrap().into_iter();
+    let int = match input.next() {
+        Some(tt) => parse_integer(&tt).ok_or_else(|| {
+            Error::new(
+                tt.span(),
+                format!(
+                    "'tt_bytes_equal_int' expects an integer literal as the first token tree \
+                     but received '{}'",
+                    tt
+                ),
+            )
+        })?,
+        None => {
+            return Err(Error::new(
+                Span::call_site(),
+                "'tt_bytes_equal_int' expects an integer literal as the first token tree \
+                 but received none."
+                    .to_string(),
+            ))
+        }
+    };
+    let array = input.next().ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "'tt_bytes_equal_int' expects a byte array after the integer but received none."
+                .to_string(),
+        )
+    })?;
+    if let Some(x) = input.next() {
+        return Err(Error::new(
+            x.span(),
+            format!(
+                "'tt_bytes_equal_int' expects only an integer and a byte array \
+                 but received more: '{}'",
+                x
+            ),
+        ));
+    }
+    let array = expect_group("tt_bytes_equal_int", array, Delimiter::Bracket)?;
+    let mut bytes = Vec::new();
+    let mut array = array.into_iter();
+    while let Some(tt) = array.next() {
+        let byte = parse_integer(&tt)
+            .filter(|byte| *byte <= u128::from(u8::MAX))
+            .ok_or_else(|| {
+                Error::new(
+                    tt.span(),
+                    format!(
+                        "'tt_bytes_equal_int' expects the array to contain only byte literals \
+                         but received '{}'",
+                        tt
+                    ),
+                )
+            })?;
+        bytes.push(byte as u8);
+        // Bytes and commas must alternate, allowing only a trailing comma.
+        match array.next() {
+            Some(TokenTree::Punct(comma)) if comma.as_char() == ',' => (),
+            Some(tt) => {
+                return Err(Error::new(
+                    tt.span(),
+                    format!(
+                        "'tt_bytes_equal_int' expects the bytes of the array to be separated \
+                         by commas but received '{}'",
+                        tt
+                    ),
+                ))
+            }
+            None => break,
+        }
+    }
+    if !little_endian {
+        bytes.reverse();
+    }
+
+    let mut remaining = int;
+    let mut is_equal = true;
+    for byte in bytes {
+        is_equal &= (remaining & 0xFF) as u8 == byte;
+        remaining = remaining.checked_shr(8).unwrap_or(0);
+    }
+    Ok(return_to_tt(caller, "is_equal", is_equal && remaining == 0))
+}
+
+///
+/// A predicate for whether a derive attribute derives all the given traits.
+/// <sup>**[[tt-call](https://docs.rs/tt-call/)]**</sup>
+///
+/// Given a `#[derive(..)]` attribute and a comma-separated list of required traits,
+/// returns whether every required trait is in the attribute's derive list.
+/// The order of the traits is irrelevant and the derive list may contain traits that
+/// are not required.
+/// Traits are compared by their path, so `Clone` and `std::clone::Clone` are different traits.
+///
+/// # Input
+///
+/// - `input = [{` a derive attribute `}]`
+/// - `required = [{` the comma-separated traits the attribute must derive `}]`
+///
+/// # Output
+///
+/// - `contains = [{` either true or false `}]`
+///
+/// # Example
+///
+/// ```
+/// use tt_equal::tt_derives_contains;
+/// use tt_call::tt_call;
+///
+/// macro_rules! bool_const{
+///     {
+///         name = [{ $name:ident }]
+///         contains = [{ $contains:tt }]
+///     } => {
+///         const $name: bool = $contains;
+///     }
+/// }
+///
+/// tt_call!{
+///     macro = [{ tt_derives_contains }]
+///     input = [{ #[derive(Debug, Clone, PartialEq)] }]
+///     required = [{ PartialEq, Debug }]
+///     ~~> bool_const!{
+///         name = [{ DERIVES_REQUIRED }]
+///     }
+/// }
+///
+/// tt_call!{
+///     macro = [{ tt_derives_contains }]
+///     input = [{ #[derive(Debug, Clone, PartialEq)] }]
+///     required = [{ Debug, Hash }]
+///     ~~> bool_const!{
+///         name = [{ MISSING_REQUIRED }]
+///     }
+/// }
+///
+/// fn main() {
+///     assert_eq!(DERIVES_REQUIRED, true);
+///     assert_eq!(MISSING_REQUIRED, false);
+/// }
+///
+/// ```
+#[proc_macro]
+pub fn tt_derives_contains(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_tt_derives_contains(item.into())
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+///
+/// Expands 'tt_derives_contains', returning an error if its input is malformed.
+///
+fn expand_tt_derives_contains(item: TokenStream) -> Result<TokenStream, Error> {
+    let (caller, mut values, _) =
+        validate_key_values("tt_derives_contains", item, &["input", "required"], &[])?;
+    let required = split_by_comma(values.pop().unwrap());
+    let mut attribute = values.pop().unwrap().into_iter();
+
+    let hash = attribute.next();
+    if hash.as_ref().map(TokenTree::to_string).as_deref() != Some("#") {
+        return Err(Error::new(
+            hash.as_ref().map_or_else(Span::call_site, TokenTree::span),
+            format!(
+                "'tt_derives_contains' expects a derive attribute starting with '#' \
+                 but received '{}'",
+                hash.map(|tt| tt.to_string()).unwrap_or_default()
+            ),
+        ));
+    }
+    let attribute_body = attribute.next().ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "'tt_derives_contains' expects a derive attribute but received only '#'.".to_string(),
+        )
+    })?;
+    let mut attribute_body =
+        expect_group("tt_derives_contains", attribute_body, Delimiter::Bracket)?.into_iter();
+    if let Some(x) = attribute.next() {
+        return Err(Error::new(
+            x.span(),
+            format!(
+                "'tt_derives_contains' expects only a single derive attribute \
+                 but received more: '{}'",
+                x
+            ),
+        ));
+    }
+    let derive = attribute_body.next();
+    if derive.as_ref().map(TokenTree::to_string).as_deref() != Some("derive") {
+        return Err(Error::new(
+            derive
+                .as_ref()
+                .map_or_else(Span::call_site, TokenTree::span),
+            format!(
+                "'tt_derives_contains' expects a 'derive' attribute but received '{}'",
+                derive.map(|tt| tt.to_string()).unwrap_or_default()
+            ),
+        ));
+    }
+    let derived = attribute_body.next().ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "'tt_derives_contains' expects the derived traits after 'derive'.".to_string(),
+        )
+    })?;
+    let derived = split_by_comma(expect_group(
+        "tt_derives_contains",
+        derived,
+        Delimiter::Parenthesis,
+    )?);
+
+    Ok(return_to_tt(
+        caller,
+        "contains",
+        required.iter().all(|trait_path| {
+            derived
+                .iter()
+                .any(|derived_path| token_strings(derived_path) == token_strings(trait_path))
+        }),
+    ))
+}
+
+///
+/// A predicate for whether an integer literal fits in an integer type.
+/// <sup>**[[tt-call](https://docs.rs/tt-call/)]**</sup>
+///
+/// Given an integer literal, optionally negated, and the name of a primitive integer type,
+/// returns whether the literal's value is within the range of the type.
+/// Any type suffix on the literal is ignored, only its value is checked.
+///
+/// `usize` and `isize` are not supported, as their width depends on the compilation target.
+///
+/// # Input
+///
+/// - `input = [{` an integer literal, optionally preceded by `-`, followed by an integer type `}]`
+///
+/// # Output
+///
+/// - `fits = [{` either true or false `}]`
+///
+/// # Example
+///
+/// ```
+/// use tt_equal::tt_fits_type;
+/// use tt_call::tt_if;
+///
+/// tt_if!{
+///     condition = [{tt_fits_type}]
+///     input = [{ 0xFF u8 }]
+///     true = [{
+///         const SHOULD_BE_TRUE: bool = true;
+///     }]
+///     false = [{
+///         const SHOULD_BE_TRUE: bool = false;
+///     }]
+/// }
+///
+/// tt_if!{
+///     condition = [{tt_fits_type}]
+///     input = [{ -129 i8 }]
+///     true = [{
+///         const SHOULD_BE_FALSE: bool = true;
+///     }]
+///     false = [{
+///         const SHOULD_BE_FALSE: bool = false;
+///     }]
+/// }
+///
+/// fn main() {
+///     assert_eq!(SHOULD_BE_TRUE, true);
+///     assert_eq!(SHOULD_BE_FALSE, false);
+/// }
+///
+/// ```
+#[proc_macro]
+pub fn tt_fits_type(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_tt_fits_type(item.into())
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+///
+/// Expands 'tt_fits_type', returning an error if its input is malformed.
+///
+fn expand_tt_fits_type(item: TokenStream) -> Result<TokenStream, Error> {
+    let (caller, mut values, _) = validate_key_values("tt_fits_type", item, &["input"], &[])?;
+    let mut input = values.remove(0).into_iter().peekable();
+
+    let negative = match input.peek() {
+        Some(TokenTree::Punct(p)) if p.as_char() == '-' => {
+            input.next();
+            true
+        }
+        _ => false,
+    };
+    let magnitude = match input.next() {
+        Some(tt) => parse_integer(&tt).ok_or_else(|| {
+            Error::new(
+                tt.span(),
+                format!(
+                    "'tt_fits_type' expects an integer literal to check but received '{}'",
+                    tt
+                ),
+            )
+        })?,
+        None => {
+            return Err(Error::new(
+                Span::call_site(),
+                "'tt_fits_type' expects an integer literal to check but received none.".to_string(),
+            ))
+        }
+    };
+    let ty = input.next().ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "'tt_fits_type' expects an integer type after the literal but received none."
+                .to_string(),
+        )
+    })?;
+    if let Some(x) = input.next() {
+        return Err(Error::new(
+            x.span(),
+            format!(
+                "'tt_fits_type' expects only an integer literal and a type but received more: '{}'",
+                x
+            ),
+        ));
+    }
+    let (max_negative, max_positive) = integer_type_range(&ty.to_string()).ok_or_else(|| {
+        Error::new(
+            ty.span(),
+            format!(
+                "'tt_fits_type' expects a fixed-width integer type but received '{}'",
+                ty
+            ),
+        )
+    })?;
+
+    Ok(return_to_tt(
+        caller,
+        "fits",
+        if negative {
+            magnitude <= max_negative
+        } else {
+            magnitude <= max_positive
+        },
+    ))
+}
+
+///
+/// A predicate for whether two comma-separated sequences contain the same elements,
+/// regardless of order.
+/// <sup>**[[tt-call](https://docs.rs/tt-call/)]**</sup>
+///
+/// Given two groups, each containing a comma-separated sequence, returns whether the sequences
+/// are equal as multisets. I.e. each element must occur the same number of times in both
+/// sequences, but the order of the elements is irrelevant.
+/// Elements may consist of multiple token trees and are compared like `tt_equal` compares
+/// token trees.
+///
+/// # Input
+///
+/// - `input = [{` exactly two groups, each containing a comma-separated sequence `}]`
+///
+/// # Output
+///
+/// - `is_equal = [{` either true or false `}]`
+///
+/// # Example
+///
+/// ```
+/// use tt_equal::tt_multiset_equal;
+/// use tt_call::tt_if;
+///
+/// tt_if!{
+///     condition = [{tt_multiset_equal}]
+///     input = [{ (a, b, a) (b, a, a) }]
+///     true = [{
+///         const SHOULD_BE_TRUE: bool = true;
+///     }]
+///     false = [{
+///         const SHOULD_BE_TRUE: bool = false;
+///     }]
+/// }
+///
+/// tt_if!{
+///     condition = [{tt_multiset_equal}]
+///     input = [{ (a, b, a) (a, b, b) }]
+///     true = [{
+///         const SHOULD_BE_FALSE: bool = true;
+///     }]
+///     false = [{
+///         const SHOULD_BE_FALSE: bool = false;
+///     }]
+/// }
+///
+/// fn main() {
+///     assert_eq!(SHOULD_BE_TRUE, true);
+///     assert_eq!(SHOULD_BE_FALSE, false);
+/// }
+///
+/// ```
+#[proc_macro]
+pub fn tt_multiset_equal(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_tt_multiset_equal(item.into()).into()
+}
+
+///
+/// Expands 'tt_multiset_equal', returning a `compile_error!` invocation if its input is malformed.
+///
+fn expand_tt_multiset_equal(item: TokenStream) -> TokenStream {
+    let (caller, lhs, rhs) = match validate_sequences("tt_multiset_equal", item) {
+        Ok(validated) => validated,
+        Err(error) => return error.into_compile_error(),
+    };
+
+    let (only_left, only_right) = multiset_difference(lhs, rhs);
+
+    return_to_tt(
+        caller,
+        "is_equal",
+        only_left.is_empty() && only_right.is_empty(),
+    )
+}
+
+///
+/// Returns the elements of two comma-separated sequences that are not in the other sequence.
+/// <sup>**[[tt-call](https://docs.rs/tt-call/)]**</sup>
+///
+/// Given two groups, each containing a comma-separated sequence, returns the multiset
+/// differences of the sequences: the elements of the left sequence that are not in the right
+/// sequence, and vice versa.
+/// Elements are matched regardless of order, but each element can only be matched once,
+/// so e.g. the difference of `(a, a)` and `(a)` is `a`.
+/// Elements may consist of multiple token trees and are compared like `tt_equal` compares
+/// token trees.
+/// The returned elements are in the order they appear in their sequence.
+///
+/// # Input
+///
+/// - `input = [{` exactly two groups, each containing a comma-separated sequence `}]`
+///
+/// # Output
+///
+/// - `only_left = [{` the comma-separated elements only in the left sequence `}]`
+/// - `only_right = [{` the comma-separated elements only in the right sequence `}]`
+///
+/// # Example
+///
+/// ```
+/// use tt_equal::tt_difference;
+/// use tt_call::tt_call;
+///
+/// macro_rules! removed_and_added{
+///     {
+///         only_left = [{ $($removed:ident),* }]
+///         only_right = [{ $($added:ident),* }]
+///     } => {
+///         const REMOVED: &[&str] = &[$(stringify!($removed)),*];
+///         const ADDED: &[&str] = &[$(stringify!($added)),*];
+///     }
+/// }
+///
+/// tt_call!{
+///     macro = [{ tt_difference }]
+///     input = [{ (a, b, c, c) (c, d, a) }]
+///     ~~> removed_and_added
+/// }
+///
+/// fn main() {
+///     assert_eq!(REMOVED, &["b", "c"]);
+///     assert_eq!(ADDED, &["d"]);
+/// }
+///
+/// ```
+#[proc_macro]
+pub fn tt_difference(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_tt_difference(item.into()).into()
+}
+
+///
+/// Expands 'tt_difference', returning a `compile_error!` invocation if its input is malformed.
+///
+fn expand_tt_difference(item: TokenStream) -> TokenStream {
+    let (caller, lhs, rhs) = match validate_sequences("tt_difference", item) {
+        Ok(validated) => validated,
+        Err(error) => return error.into_compile_error(),
+    };
+    let (only_left, only_right) = multiset_difference(lhs, rhs);
+
+    return_values_to_tt(
+        caller,
+        vec![
+            ("only_left", join_by_comma(only_left)),
+            ("only_right", join_by_comma(only_right)),
+        ],
+    )
+}
+
+///
+/// A predicate for whether two comma-separated sequences are similar.
+/// <sup>**[[tt-call](https://docs.rs/tt-call/)]**</sup>
+///
+/// Given two groups, each containing a comma-separated sequence, returns whether the
+/// Jaccard similarity of the sequences, as multisets, is at least the given threshold.
+/// The similarity is the number of elements the sequences have in common divided by
+/// the number of elements in their union, i.e. `|A ∩ B| / |A ∪ B|`, where an element occurring
+/// multiple times in both sequences is in common as many times as it occurs in both.
+/// Two empty sequences have a similarity of 1.
+/// Elements may consist of multiple token trees and are compared like `tt_equal` compares
+/// token trees.
+///
+/// # Input
+///
+/// - `input = [{` exactly two groups, each containing a comma-separated sequence `}]`
+/// - `threshold = [{` the minimum similarity, as a number between 0 and 1 `}]`
+///
+/// # Output
+///
+/// - `is_similar = [{` either true or false `}]`
+///
+/// # Example
+///
+/// ```
+/// use tt_equal::tt_similar;
+/// use tt_call::tt_call;
+///
+/// macro_rules! bool_const{
+///     {
+///         name = [{ $name:ident }]
+///         is_similar = [{ $is_similar:tt }]
+///     } => {
+///         const $name: bool = $is_similar;
+///     }
+/// }
+///
+/// tt_call!{
+///     macro = [{ tt_similar }]
+///     input = [{ (a, b, c, d, e) (a, b, c, d, f) }]   // 4 in common, 6 in the union
+///     threshold = [{ 0.6 }]
+///     ~~> bool_const!{
+///         name = [{ SHOULD_BE_TRUE }]
+///     }
+/// }
+///
+/// tt_call!{
+///     macro = [{ tt_similar }]
+///     input = [{ (a, b, c, d, e) (a, b, c, d, f) }]
+///     threshold = [{ 0.8 }]
+///     ~~> bool_const!{
+///         name = [{ SHOULD_BE_FALSE }]
+///     }
+/// }
+///
+/// fn main() {
+///     assert_eq!(SHOULD_BE_TRUE, true);
+///     assert_eq!(SHOULD_BE_FALSE, false);
+/// }
+///
+/// ```
+#[proc_macro]
+pub fn tt_similar(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_tt_similar(item.into()).into()
+}
+
+///
+/// Expands 'tt_similar', returning a `compile_error!` invocation if its input is malformed.
+///
+fn expand_tt_similar(item: TokenStream) -> TokenStream {
+    let (caller, mut values, _) =
+        match validate_key_values("tt_similar", item, &["input", "threshold"], &[]) {
+            Ok(validated) => validated,
+            Err(error) => return error.into_compile_error(),
+        };
+    let threshold = values.pop().unwrap();
+    let threshold = match threshold.to_string().trim().parse::<f64>() {
+        Ok(threshold) => threshold,
+        Err(_) => {
+            return Error::new(
+                threshold
+                    .into_iter()
+                    .next()
+                    .map_or_else(Span::call_site, |tt| tt.span()),
+                "'tt_similar' expects its threshold to be a number.".to_string(),
+            )
+            .into_compile_error()
+        }
+    };
+    let (lhs, rhs) = match split_sequences("tt_similar", values.pop().unwrap()) {
+        Ok(sequences) => sequences,
+        Err(error) => return error.into_compile_error(),
+    };
+    let lhs_len = lhs.len();
+    let (only_left, only_right) = multiset_difference(lhs, rhs);
+
+    let common = lhs_len - only_left.len();
+    let union = lhs_len + only_right.len();
+    let similarity = if union == 0 {
+        1.0
+    } else {
+        common as f64 / union as f64
+    };
+
+    return_to_tt(caller, "is_similar", similarity >= threshold)
+}
+
+///
+/// The callers opaque tt bundle, the two sides, the mode, the maximum number of tokens,
+/// and the values of any extra optional keys, as returned by `validate`.
+///
+type Validated = (
+    TokenTree,
+    TokenStream,
+    TokenStream,
+    Mode,
+    usize,
+    Vec<Option<TokenStream>>,
+);
+
+///
+/// Validates that the input to the given comparison macro (e.g. 'tt_equal') is correct and returns:
+/// 0. The callers opaque tt bundle
+/// 1. The left-hand side of the input to compare
+/// 2. The right-hand side of the input to compare
+/// 3. The mode to compare in
+/// 4. The maximum number of tokens the input may contain
+/// 5. The values of the given extra optional keys, in the same order
+///
+fn validate(macro_name: &str, item: TokenStream, extra_keys: &[&str]) -> Result<Validated, Error> {
+    let (caller, sides, mode, max_tokens, extra_values) =
+        validate_sides(macro_name, item, extra_keys)?;
+    let span_of = |side: &TokenStream| {
+        side.clone()
+            .into_iter()
+            .next()
+            .map_or_else(Span::call_site, |tt| tt.span())
+    };
+    match sides.len() {
+        0 => Err(Error::new(
+            Span::call_site(),
+            format!(
+                "'{}' expects two token tree to compare but received none.",
+                macro_name
+            ),
+        )),
+        1 => Err(Error::new(
+            span_of(&sides[0]),
+            format!(
+                "'{}' expects two token tree to compare but received only one",
+                macro_name
+            ),
+        )),
+        2 => {
+            let mut sides = sides.into_iter();
+            let lhs = sides.next().unwrap();
+            let rhs = sides.next().unwrap();
+            Ok((caller, lhs, rhs, mode, max_tokens, extra_values))
+        }
+        _ => Err(Error::new(
+            span_of(&sides[2]),
+            format!(
+                "'{}' expects two token tree to compare but received more: '{} {} {}'",
+                macro_name, sides[0], sides[1], sides[2]
+            ),
+        )),
+    }
+}
+
+///
+/// The callers opaque tt bundle, the token trees of the input, the mode, the maximum number
+/// of tokens, and the values of any extra optional keys, as returned by `validate_sides`.
+///
+type Sides = (
+    TokenTree,
+    Vec<TokenStream>,
+    Mode,
+    usize,
+    Vec<Option<TokenStream>>,
+);
+
+///
+/// Validates that the input to the given comparison macro (e.g. 'tt_all_equal') is correct
+/// and returns:
+/// 0. The callers opaque tt bundle
+/// 1. Each token tree of the input, where a multi-character punctuation is a single token tree
+///    (see `get_next_joint_token_in_mode`)
+/// 2. The mode to compare in
+/// 3. The maximum number of tokens the input may contain
+/// 4. The values of the given extra optional keys, in the same order
+///
+fn validate_sides(
+    macro_name: &str,
+    item: TokenStream,
+    extra_keys: &[&str],
+) -> Result<Sides, Error> {
+    let optional_keys = Vec::from_iter(
+        ["mode", "defaults", "max_tokens"]
+            .iter()
+            .chain(extra_keys)
+            .copied(),
+    );
+    let (caller, mut values, mut optional_values) =
+        validate_key_values(macro_name, item, &["input"], &optional_keys)?;
+    let mode = optional_values.remove(0).unwrap_or_default();
+    let defaults = optional_values.remove(0).unwrap_or_default();
+    let mode = parse_mode(macro_name, defaults.into_iter().chain(mode).collect())?;
+    let max_tokens = match optional_values.remove(0) {
+        Some(max_tokens) => parse_usize(macro_name, "max_tokens", max_tokens)?,
+        None => DEFAULT_MAX_TOKENS,
+    };
+    let input = values.remove(0);
+    if let Some(TokenTree::Punct(last)) = input.clone().into_iter().last() {
+        // 'macro_rules!' passes on a punctuation with the spacing it was written with, so e.g.
+        // the '=' of '$op:tt' given '=;' is joint, even though it ends the input.
+        if last.spacing() == Spacing::Joint {
+            return Err(Error::new(
+                last.span(),
+                format!(
+                    "'{}' expects every joint punctuation to be followed by another punctuation \
+                     but the input ends in a joint '{}'",
+                    macro_name,
+                    last.as_char()
+                ),
+            ));
+        }
+    }
+    let mut clean_value = input.into_iter().peekable();
+    let mut sides = Vec::new();
+    while let Some(side) = get_next_joint_token_in_mode(&mut clean_value, &mode) {
+        sides.push(side.into_iter().collect());
+    }
+    Ok((caller, sides, mode, max_tokens, optional_values))
+}
+
+///
+/// The maximum number of tokens a comparison macro's input may contain if not given
+/// by the 'max_tokens' key.
+///
+const DEFAULT_MAX_TOKENS: usize = 100_000;
+
+///
+/// Counts the tokens in the given streams, including the tokens nested in groups and
+/// the groups themselves, returning an error if more than the given maximum are found.
+///
+/// Counting stops as soon as the maximum is exceeded and is done without recursion,
+/// such that deeply nested input can't exhaust the stack.
+///
+fn check_max_tokens(
+    macro_name: &str,
+    streams: &[&TokenStream],
+    max_tokens: usize,
+) -> Result<(), Error> {
+    let mut count = 0;
+    let mut iters = Vec::from_iter(streams.iter().map(|stream| (*stream).clone().into_iter()));
+    while let Some(iter) = iters.last_mut() {
+        match iter.next() {
+            Some(tt) => {
+                count += 1;
+                if count > max_tokens {
+                    return Err(Error::new(
+                        Span::call_site(),
+                        format!(
+                            "'{}' received more than the maximum of {} tokens.",
+                            macro_name, max_tokens
+                        ),
+                    ));
+                }
+                if let TokenTree::Group(group) = tt {
+                    iters.push(group.stream().into_iter());
+                }
+            }
+            None => {
+                iters.pop();
+            }
+        }
+    }
+    Ok(())
+}
+
+///
+/// The token trees of an element of a comma-separated sequence.
+///
+type Element = Vec<TokenTree>;
+
+///
+/// Validates that the input to the given macro is exactly two groups, each containing
+/// a comma-separated sequence, and returns:
+/// 0. The callers opaque tt bundle
+/// 1. The elements of the left-hand sequence
+/// 2. The elements of the right-hand sequence
+///
+fn validate_sequences(
+    macro_name: &str,
+    item: TokenStream,
+) -> Result<(TokenTree, Vec<Element>, Vec<Element>), Error> {
+    let (caller, mut values, _) = validate_key_values(macro_name, item, &["input"], &[])?;
+    let (lhs, rhs) = split_sequences(macro_name, values.remove(0))?;
+    Ok((caller, lhs, rhs))
+}
+
+///
+/// Validates that the given input value is exactly two groups, each containing
+/// a comma-separated sequence, and returns the elements of each sequence.
+///
+fn split_sequences(
+    macro_name: &str,
+    input: TokenStream,
+) -> Result<(Vec<Element>, Vec<Element>), Error> {
+    let mut sequences = input.into_iter();
+    let mut next_sequence = |count: &str| match sequences.next() {
+        Some(TokenTree::Group(g)) => Ok(split_by_comma(g.stream())),
+        Some(tt) => Err(Error::new(
+            tt.span(),
+            format!(
+                "'{}' expects each sequence to be within a group but got '{}'",
+                macro_name, tt
+            ),
+        )),
+        None => Err(Error::new(
+            Span::call_site(),
+            format!(
+                "'{}' expects two sequences to compare but received {}.",
+                macro_name, count
+            ),
+        )),
+    };
+    let lhs = next_sequence("none")?;
+    let rhs = next_sequence("only one")?;
+    if let Some(x) = sequences.next() {
+        return Err(Error::new(
+            x.span(),
+            format!(
+                "'{}' expects two sequences to compare but received more.",
+                macro_name
+            ),
+        ));
+    }
+    Ok((lhs, rhs))
+}
+
+///
+/// The callers opaque tt bundle, the values of the required keys, and the values of the
+/// optional keys, as returned by `validate_key_values`.
+///
+type KeyValues = (TokenTree, Vec<TokenStream>, Vec<Option<TokenStream>>);
+
+///
+/// Validates that the input to the given macro is the callers opaque tt bundle followed by
+/// exactly the given keys' key-value pairs and, optionally, the given optional keys' key-value
+/// pairs (in any order) and returns:
+/// 0. The callers opaque tt bundle
+/// 1. The contents of each key's value, in the same order as the given keys
+/// 2. The contents of each optional key's value, if given, in the same order as the
+///    given optional keys
+///
+fn validate_key_values(
+    macro_name: &str,
+    item: TokenStream,
+    keys: &[&str],
+    optional_keys: &[&str],
+) -> Result<KeyValues, Error> {
+    let mut iter = item.into_iter();
+
+    let caller = iter.next().ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            format!("'{}' did not receive caller's tt bundle.", macro_name),
+        )
+    })?;
+    let all_keys: Vec<_> = keys.iter().chain(optional_keys).collect();
+    let mut values: Vec<Option<TokenStream>> = vec![None; all_keys.len()];
+    while let Some(key) = iter.next() {
+        let key_name = key.to_string();
+        let index = all_keys
+            .iter()
+            .position(|k| **k == key_name.trim())
+            .ok_or_else(|| {
+                Error::new(
+                    key.span(),
+                    format!(
+                        "'{}' expects its input's keys to be named {:?} but received '{}'",
+                        macro_name,
+                        all_keys,
+                        key_name.trim()
+                    ),
+                )
+            })?;
+        let separator = iter.next().ok_or_else(|| {
+            Error::new(
+                key.span(),
+                format!(
+                    "'{}' expects a key value pair as input but did not receive it.",
+                    macro_name
+                ),
+            )
+        })?;
+        if separator.to_string() != "=" {
+            return Err(Error::new(
+                separator.span(),
+                format!(
+                    "'{}' expects its input key-value pairs to be separated by a '=' \
+                     but instead received '{}'",
+                    macro_name, separator
+                ),
+            ));
+        }
+        let value_group = iter.next().ok_or_else(|| {
+            Error::new(
+                separator.span(),
+                format!(
+                    "'{}' expects a key-value pair as input but received no value.",
+                    macro_name
+                ),
+            )
+        })?;
+        let value_span = value_group.span();
+        let mut unbracketed_group =
+            expect_group(macro_name, value_group, Delimiter::Bracket)?.into_iter();
+        let braced_group = unbracketed_group.next().ok_or_else(|| {
+            Error::new(
+                value_span,
+                format!(
+                    "'{}' expects its input value to be within '{}' but the '{}' was not given.",
+                    macro_name, "[{..}]", "{..}"
+                ),
+            )
+        })?;
+        if let Some(x) = unbracketed_group.next() {
+            return Err(Error::new(
+                x.span(),
+                format!(
+                    "'{}' expects its input value to be within '{}' \
+                     but it received additional tokens after the braces ('{}').",
+                    macro_name, "[{..}]", "{..}"
+                ),
+            ));
+        }
+        if values[index].is_some() {
+            return Err(Error::new(
+                key.span(),
+                format!(
+                    "'{}' expects only one key-value pair named '{}' but received more.",
+                    macro_name,
+                    key_name.trim()
+                ),
+            ));
+        }
+        values[index] = Some(expect_group(macro_name, braced_group, Delimiter::Brace)?);
+    }
+    let optional_values = values.split_off(keys.len());
+    let values = values
+        .into_iter()
+        .zip(keys)
+        .map(|(value, key)| {
+            value.ok_or_else(|| {
+                Error::new(
+                    Span::call_site(),
+                    format!(
+                        "'{}' expects a key-value pair named '{}' but did not receive it.",
+                        macro_name, key
+                    ),
+                )
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((caller, values, optional_values))
+}
+
+///
+/// Splits the contents of the group that makes up the given side into its joint token trees
+/// (see `get_next_joint_token`).
+///
+fn joint_tokens_in_group(
+    macro_name: &str,
+    side: TokenStream,
+) -> Result<Vec<Vec<TokenTree>>, Error> {
+    match side.into_iter().next() {
+        Some(TokenTree::Group(group)) => {
+            let mut tokens = group.stream().into_iter().peekable();
+            Ok(std::iter::from_fn(|| get_next_joint_token(&mut tokens)).collect())
+        }
+        Some(tt) => Err(Error::new(
+            tt.span(),
+            format!(
+                "'{}' expects each side to be within a group but got '{}'",
+                macro_name, tt
+            ),
+        )),
+        None => unreachable!(),
+    }
+}
+
+///
+/// Unwraps a token tree, assuming it has the given delimiter, and returns
+/// its contents
+///
+fn expect_group(
+    macro_name: &str,
+    tt: TokenTree,
+    expected_delimiter: Delimiter,
+) -> Result<TokenStream, Error> {
+    if let TokenTree::Group(g) = tt {
+        if expected_delimiter == g.delimiter() {
+            Ok(g.stream())
+        } else {
+            Err(Error::new(
+                g.span(),
+                format!(
+                    "'{}' expects delimiter '{:?}' but got '{:?}'.",
+                    macro_name,
+                    expected_delimiter,
+                    g.delimiter()
+                ),
+            ))
+        }
+    } else {
+        Err(Error::new(
+            tt.span(),
+            format!(
+                "'{}' expects a group of tokens inside {:?} but got '{:?}'",
+                macro_name, expected_delimiter, tt
+            ),
+        ))
+    }
+}
+
+///
+/// An error in the input to a macro.
+///
+/// Instead of panicking, a macro expands to the error's `compile_error!` invocation,
+/// such that the user gets a normal diagnostic pointing at the offending input.
+///
+struct Error {
+    span: Span,
+    message: String,
+}
+
+impl Error {
+    fn new(span: Span, message: String) -> Self {
+        Error { span, message }
+    }
+
+    ///
+    /// Returns a `compile_error!` invocation with the error's message at the error's span.
+    ///
+    fn into_compile_error(self) -> TokenStream {
+        let mut bang = Punct::new('!', Spacing::Alone);
+        bang.set_span(self.span);
+        let mut message = Literal::string(&self.message);
+        message.set_span(self.span);
+        let mut body = Group::new(
+            Delimiter::Brace,
+            TokenStream::from(TokenTree::from(message)),
+        );
+        body.set_span(self.span);
+        TokenStream::from_iter(vec![
+            TokenTree::from(Ident::new("compile_error", self.span)),
+            TokenTree::from(bang),
+            TokenTree::from(body),
+        ])
+    }
+}
+
+///
+/// Constructs the result of a predicate, returning the given bool under the given key
+///
+fn return_to_tt(caller: TokenTree, key: &str, b: bool) -> TokenStream {
+    let value: TokenStream = b.to_string().parse().expect(
+        "'tt_equal' internal error 2.  Please file a bug with the tt-equal crate maintainers.",
+    );
+    return_values_to_tt(caller, vec![(key, value)])
+}
+
+///
+/// Constructs the result of a tt-call macro, returning each given value under its key
+///
+fn return_values_to_tt(caller: TokenTree, values: Vec<(&str, TokenStream)>) -> TokenStream {
+    let return_call: TokenStream = "tt_call::tt_return!".parse().expect(
+        "'tt_equal' internal error 1. Please file a bug with the tt-equal crate maintainers.",
+    );
+
+    let mut return_body: Vec<_> = Vec::new();
+    return_body.push(caller);
+    for (key, value) in values {
+        let braced_value = TokenTree::from(Group::new(Delimiter::Brace, value));
+        return_body.push(TokenTree::from(Ident::new(key, Span::call_site())));
+        return_body.push(TokenTree::from(Punct::new('=', Spacing::Alone)));
+        return_body.push(TokenTree::from(Group::new(
+            Delimiter::Bracket,
+            TokenStream::from(braced_value),
+        )));
+    }
+    let return_call_argument = TokenTree::from(Group::new(
+        Delimiter::Brace,
+        TokenStream::from_iter(return_body),
+    ));
+
+    let mut result: Vec<TokenTree> = Vec::new();
+    result.extend(return_call);
+    result.push(return_call_argument);
+
+    TokenStream::from_iter(result)
+}
+
+///
+/// Parses an integer literal token into its value, ignoring any type suffix.
+///
+/// If the token is not an integer literal, or the value doesn't fit in `u128`, `None` is returned.
+///
+fn parse_integer(tt: &TokenTree) -> Option<u128> {
+    if let TokenTree::Literal(lit) = tt {
+        parse_integer_literal(&lit.to_string()).map(|(value, _)| value)
+    } else {
+        None
+    }
+}
+
+///
+/// Returns the largest magnitude of a negative and of a positive value of the given fixed-width
+/// integer type, or `None` if the name is not such a type.
+///
+fn integer_type_range(ty: &str) -> Option<(u128, u128)> {
+    Some(match ty {
+        "u8" => (0, u8::MAX.into()),
+        "u16" => (0, u16::MAX.into()),
+        "u32" => (0, u32::MAX.into()),
+        "u64" => (0, u64::MAX.into()),
+        "u128" => (0, u128::MAX),
+        "i8" => (1 << 7, i8::MAX as u128),
+        "i16" => (1 << 15, i16::MAX as u128),
+        "i32" => (1 << 31, i32::MAX as u128),
+        "i64" => (1 << 63, i64::MAX as u128),
+        "i128" => (1 << 127, i128::MAX as u128),
+        _ => return None,
+    })
+}
+
+///
+/// Parses the value of the given macro's 'expected_type' key into the range of the type
+/// (see `integer_type_range`).
+///
+fn parse_integer_type(macro_name: &str, value: TokenStream) -> Result<(u128, u128), Error> {
+    let value = Vec::from_iter(value);
+    match value.as_slice() {
+        [TokenTree::Ident(ty)] => integer_type_range(&ty.to_string()),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        Error::new(
+            value.first().map_or_else(Span::call_site, TokenTree::span),
+            format!(
+                "'{}' expects 'expected_type' to be a fixed-width integer type but received: '{}'",
+                macro_name,
+                TokenStream::from_iter(value.clone())
+            ),
+        )
+    })
+}
+
+///
+/// If the given side is an integer literal, optionally preceded by `-`, returns its value as
+/// an unsuffixed decimal literal, such that literals of equal value compare equal.
+/// Returns an error if the value is outside the given range (see `integer_type_range`).
+///
+/// Any other side is returned as is.
+///
+fn integer_in_range(
+    macro_name: &str,
+    side: &TokenStream,
+    (max_negative, max_positive): (u128, u128),
+) -> Result<TokenStream, Error> {
+    let tokens = Vec::from_iter(side.clone());
+    let (negative, lit) = match tokens.as_slice() {
+        [TokenTree::Literal(lit)] => (false, lit),
+        [TokenTree::Punct(minus), TokenTree::Literal(lit)] if minus.as_char() == '-' => (true, lit),
+        _ => return Ok(side.clone()),
+    };
+    let value = match parse_integer_literal(&lit.to_string()) {
+        Some((value, _)) => value,
+        None => return Ok(side.clone()),
+    };
+    if value > if negative { max_negative } else { max_positive } {
+        return Err(Error::new(
+            lit.span(),
+            format!(
+                "'{}' expects integer literals to fit in 'expected_type' but received: '{}{}'",
+                macro_name,
+                if negative { "-" } else { "" },
+                lit
+            ),
+        ));
+    }
+    let mut canonical = Literal::u128_unsuffixed(value);
+    canonical.set_span(lit.span());
+    let mut tokens = Vec::new();
+    if negative && value != 0 {
+        tokens.push(TokenTree::from(Punct::new('-', Spacing::Alone)));
+    }
+    tokens.push(TokenTree::from(canonical));
+    Ok(TokenStream::from_iter(tokens))
+}
+
+///
+/// Splits the token stream at each top-level comma, returning the token trees between them.
+///
+/// A trailing comma does not result in an empty element at the end.
+///
+fn split_by_comma(stream: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut elements = vec![Vec::new()];
+    for tt in stream {
+        match &tt {
+            TokenTree::Punct(p) if p.as_char() == ',' => elements.push(Vec::new()),
+            _ => elements.last_mut().unwrap().push(tt),
+        }
+    }
+    if elements.last().unwrap().is_empty() {
+        elements.pop();
+    }
+    elements
+}
+
+///
+/// Returns the elements of each sequence that are not matched by an equal element in
+/// the other sequence, regardless of order:
+/// 0. The unmatched elements of the left-hand sequence
+/// 1. The unmatched elements of the right-hand sequence
+///
+/// Each element can only match one element in the other sequence, so a duplicated element
+/// is unmatched if the other sequence doesn't contain it as many times.
+/// Elements are compared as token streams (see `tokens_equal`).
+///
+fn multiset_difference(
+    lhs: Vec<Vec<TokenTree>>,
+    mut rhs: Vec<Vec<TokenTree>>,
+) -> (Vec<Vec<TokenTree>>, Vec<Vec<TokenTree>>) {
+    let mut only_lhs = Vec::new();
+    for lhs in lhs {
+        let lhs_stream = TokenStream::from_iter(lhs.iter().cloned());
+        let matched = rhs.iter().position(|rhs| {
+            tokens_equal(&lhs_stream, &TokenStream::from_iter(rhs.iter().cloned()))
+        });
+        match matched {
+            Some(i) => {
+                rhs.remove(i);
+            }
+            None => only_lhs.push(lhs),
+        }
+    }
+    (only_lhs, rhs)
+}
+
+///
+/// Joins the given elements into a comma-separated token stream.
+///
+fn join_by_comma(elements: Vec<Vec<TokenTree>>) -> TokenStream {
+    let mut tokens = Vec::new();
+    for element in elements {
+        if !tokens.is_empty() {
+            tokens.push(TokenTree::from(Punct::new(',', Spacing::Alone)));
+        }
+        tokens.extend(element);
+    }
+    TokenStream::from_iter(tokens)
+}
+
+///
+/// Parses the value of a 'mode' key, which is a list of the modes to turn on.
+///
+fn parse_mode(macro_name: &str, value: TokenStream) -> Result<Mode, Error> {
+    let mut mode = Mode::default();
+    for tt in value {
+        match mode_flag(&mut mode, tt.to_string().trim()) {
+            Some(flag) => *flag = true,
+            None => {
+                return Err(Error::new(
+                    tt.span(),
+                    format!("'{}' does not have a '{}' mode.", macro_name, tt),
+                ))
+            }
+        }
+    }
+    Ok(mode)
+}
+
+///
+/// The names of all the modes, as given in the 'mode' key.
+///
+/// Aliases of modes are not listed, such that `applied` names each mode once.
+///
+const MODES: &[&str] = &[
+    "semantic",
+    "glob_matches_explicit",
+    "normalize_bounds_position",
+    "concat_doc_attrs",
+    "case_insensitive",
+    "ignore_const_fn",
+    "normalize_extern_crate",
+    "normalize_stringify",
+    "variant_fields_positional",
+    "ignore_delimiters",
+    "header_only",
+    "strict",
+    "desugar_try",
+    "width_independent",
+    "unrooted_attr_paths",
+    "exact",
+    "normalize_line_endings",
+    "ignore_suffixes",
+    "assoc_bindings_unordered",
+    "compare_impl_subject_only",
+    "ignore_impl_generics_names",
+    "ignore_loop_labels",
+    "strip_ident_numeric_suffix",
+    "raw_insensitive",
+    "ignore_use_rename",
+    "signed_numbers",
+    "ignore_type_ascription",
+    "str_ignore_case",
+    "elide_static_str",
+    "ignore_struct_update",
+    "normalize_field_shorthand",
+    "ignore_param_mut",
+    "anon_lifetime_wildcard",
+    "ignore_let_mut",
+    "ignore_result_err",
+    "ignore_unsafe_blocks",
+    "unit_struct_forms",
+    "ignore_static_bounds",
+];
+
+///
+/// Returns the flag of the given mode that enables the mode with the given name,
+/// or `None` if there is no such mode.
+///
+/// Aliases of modes, e.g. `alpha_rename_generics`, return the flag of the mode they alias.
+///
+fn mode_flag<'a>(mode: &'a mut Mode, name: &str) -> Option<&'a mut bool> {
+    Some(match name {
+        "semantic" => &mut mode.semantic,
+        "glob_matches_explicit" => &mut mode.glob_matches_explicit,
+        "normalize_bounds_position" => &mut mode.normalize_bounds_position,
+        "concat_doc_attrs" => &mut mode.concat_doc_attrs,
+        "case_insensitive" => &mut mode.case_insensitive,
+        "ignore_const_fn" => &mut mode.ignore_const_fn,
+        "normalize_extern_crate" => &mut mode.normalize_extern_crate,
+        "normalize_stringify" => &mut mode.normalize_stringify,
+        "variant_fields_positional" => &mut mode.variant_fields_positional,
+        "ignore_delimiters" => &mut mode.ignore_delimiters,
+        "header_only" => &mut mode.header_only,
+        "strict" => &mut mode.strict,
+        "desugar_try" => &mut mode.desugar_try,
+        "width_independent" => &mut mode.width_independent,
+        "unrooted_attr_paths" => &mut mode.unrooted_attr_paths,
+        "exact" => &mut mode.exact,
+        "normalize_line_endings" => &mut mode.normalize_line_endings,
+        "ignore_suffixes" => &mut mode.ignore_suffixes,
+        "assoc_bindings_unordered" => &mut mode.assoc_bindings_unordered,
+        "compare_impl_subject_only" => &mut mode.compare_impl_subject_only,
+        "ignore_impl_generics_names" | "alpha_rename_generics" => {
+            &mut mode.ignore_impl_generics_names
+        }
+        "ignore_loop_labels" => &mut mode.ignore_loop_labels,
+        "strip_ident_numeric_suffix" => &mut mode.strip_ident_numeric_suffix,
+        "raw_insensitive" => &mut mode.raw_insensitive,
+        "ignore_use_rename" => &mut mode.ignore_use_rename,
+        "signed_numbers" => &mut mode.signed_numbers,
+        "ignore_type_ascription" => &mut mode.ignore_type_ascription,
+        "str_ignore_case" => &mut mode.str_ignore_case,
+        "elide_static_str" => &mut mode.elide_static_str,
+        "ignore_struct_update" => &mut mode.ignore_struct_update,
+        "normalize_field_shorthand" => &mut mode.normalize_field_shorthand,
+        "ignore_param_mut" => &mut mode.ignore_param_mut,
+        "anon_lifetime_wildcard" => &mut mode.anon_lifetime_wildcard,
+        "ignore_let_mut" => &mut mode.ignore_let_mut,
+        "ignore_result_err" => &mut mode.ignore_result_err,
+        "ignore_unsafe_blocks" => &mut mode.ignore_unsafe_blocks,
+        "unit_struct_forms" => &mut mode.unit_struct_forms,
+        "ignore_static_bounds" => &mut mode.ignore_static_bounds,
+        _ => return None,
+    })
+}
+
+///
+/// Parses the value of the given key as either `true` or `false`.
+///
+fn parse_bool(macro_name: &str, key: &str, value: TokenStream) -> Result<bool, Error> {
+    let value = Vec::from_iter(value);
+    match value.as_slice() {
+        [TokenTree::Ident(b)] if b == "true" => Ok(true),
+        [TokenTree::Ident(b)] if b == "false" => Ok(false),
+        _ => Err(Error::new(
+            value.first().map_or_else(Span::call_site, TokenTree::span),
+            format!(
+                "'{}' expects '{}' to be either true or false but received: '{}'",
+                macro_name,
+                key,
+                TokenStream::from_iter(value.clone())
+            ),
+        )),
+    }
+}
+
+///
+/// Replaces every `Self` identifier in the given stream, including in nested groups,
+/// with the given type.
+///
+fn substitute_self(stream: TokenStream, self_type: &TokenStream) -> TokenStream {
+    let mut result = TokenStream::new();
+    for tt in stream {
+        match tt {
+            TokenTree::Ident(ident) if ident == "Self" => result.extend(self_type.clone()),
+            TokenTree::Group(group) => {
+                let mut substituted = Group::new(
+                    group.delimiter(),
+                    substitute_self(group.stream(), self_type),
+                );
+                substituted.set_span(group.span());
+                result.extend(Some(TokenTree::Group(substituted)));
+            }
+            tt => result.extend(Some(tt)),
+        }
+    }
+    result
+}
+
+///
+/// Parses the value of the given macro's given key into a `usize`.
+///
+fn parse_usize(macro_name: &str, key: &str, value: TokenStream) -> Result<usize, Error> {
+    let value = Vec::from_iter(value);
+    match value.as_slice() {
+        [tt] => parse_integer(tt).and_then(|value| usize::try_from(value).ok()),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        Error::new(
+            value.first().map_or_else(Span::call_site, TokenTree::span),
+            format!(
+                "'{}' expects '{}' to be an integer but received: '{}'",
+                macro_name,
+                key,
+                TokenStream::from_iter(value.clone())
+            ),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///
+    /// Parses the given string into a token stream.
+    ///
+    fn parse(s: &str) -> TokenStream {
+        s.parse().unwrap()
+    }
+
+    ///
+    /// Returns the message of the error the given result holds, panicking if it holds a value.
+    ///
+    fn error_message<T>(result: Result<T, Error>) -> String {
+        match result {
+            Ok(_) => panic!("Expected an error but got a value."),
+            Err(error) => error.message,
+        }
+    }
+
+    ///
+    /// Returns the value the given result holds, panicking with the error's message otherwise.
+    ///
+    fn value<T>(result: Result<T, Error>) -> T {
+        result.unwrap_or_else(|error| panic!("Expected a value but got: {}", error.message))
+    }
+
+    ///
+    /// Returns the string representation of each of the given streams.
+    ///
+    fn strings<'a>(streams: impl IntoIterator<Item = &'a TokenStream>) -> Vec<String> {
+        streams.into_iter().map(TokenStream::to_string).collect()
+    }
+
+    ///
+    /// Tests that `validate` returns the two sides of the input and the values of the
+    /// extra keys.
+    ///
+    #[test]
+    fn test_validate() {
+        let (caller, lhs, rhs, mode, max_tokens, extra_values) = value(validate(
+            "tt_equal",
+            parse("{ caller } input = [{ a (b c) }] echo = [{ true }]"),
+            &["emit_applied", "echo"],
+        ));
+        assert_eq!(caller.to_string(), "{ caller }");
+        assert_eq!(lhs.to_string(), "a");
+        assert_eq!(rhs.to_string(), "(b c)");
+        assert!(!mode.semantic);
+        assert_eq!(max_tokens, DEFAULT_MAX_TOKENS);
+        assert!(extra_values[0].is_none());
+        assert_eq!(strings(extra_values[1].as_ref()), ["true"]);
+
+        let validate_input = |input: &str| validate("tt_equal", parse(input), &[]);
+        assert!(error_message(validate_input("c input = [{ }]")).contains("received none"));
+        assert!(error_message(validate_input("c input = [{ a }]")).contains("received only one"));
+        assert!(error_message(validate_input("c input = [{ a b c }]"))
+            .contains("received more: 'a b c'"));
+        assert!(
+            error_message(validate_input("c input = [{ a b }] echo = [{ true }]"))
+                .contains("expects its input's keys to be named")
+        );
+    }
+
+    ///
+    /// Tests that `validate_sides` splits the input into joint token trees and parses
+    /// the optional keys.
+    ///
+    #[test]
+    fn test_validate_sides() {
+        let (_, sides, mode, max_tokens, extra_values) = value(validate_sides(
+            "tt_all_equal",
+            parse("c input = [{ += 'a a - 1 }] mode = [{ semantic }] max_tokens = [{ 0x10 }]"),
+            &["echo"],
+        ));
+        assert_eq!(strings(&sides), ["+=", "'a", "a", "-", "1"]);
+        assert!(mode.semantic);
+        assert_eq!(max_tokens, 16);
+        assert!(extra_values[0].is_none());
+
+        let (_, sides, mode, _, _) = value(validate_sides(
+            "tt_all_equal",
+            parse("c mode = [{ signed_numbers }] defaults = [{ semantic }] input = [{ - 1 +2 }]"),
+            &[],
+        ));
+        assert_eq!(strings(&sides), ["- 1", "+ 2"]);
+        assert!(mode.signed_numbers && mode.semantic);
+
+        let validate_input = |input: TokenStream| validate_sides("tt_all_equal", input, &[]);
+        assert!(error_message(validate_input(parse(
+            "c input = [{ a }] mode = [{ fuzzy }]"
+        )))
+        .contains("does not have a 'fuzzy' mode"));
+        assert!(error_message(validate_input(parse(
+            "c input = [{ a }] max_tokens = [{ x }]"
+        )))
+        .contains("expects 'max_tokens' to be an integer but received: 'x'"));
+
+        // A joint punctuation can only end the input if the stream is constructed by hand,
+        // or passed on by 'macro_rules!' from before another punctuation.
+        let mut trailing_joint = Vec::from_iter(parse("c input = [{ a }]"));
+        let value = TokenStream::from_iter(vec![
+            TokenTree::from(Ident::new("a", Span::call_site())),
+            TokenTree::from(Punct::new('+', Spacing::Joint)),
+        ]);
+        trailing_joint[3] = TokenTree::from(Group::new(
+            Delimiter::Bracket,
+            TokenStream::from(TokenTree::from(Group::new(Delimiter::Brace, value))),
+        ));
+        assert!(
+            error_message(validate_input(TokenStream::from_iter(trailing_joint)))
+                .contains("the input ends in a joint '+'")
+        );
+    }
+
+    ///
+    /// Tests that `validate_key_values` returns the values of the given keys in order,
+    /// regardless of the order they are given in.
+    ///
+    #[test]
+    fn test_validate_key_values() {
+        let (caller, values, optional_values) = value(validate_key_values(
+            "tt_difference",
+            parse("c rhs = [{ b }] lhs = [{ a a }] extra = [{ }]"),
+            &["lhs", "rhs"],
+            &["extra", "missing"],
+        ));
+        assert_eq!(caller.to_string(), "c");
+        assert_eq!(strings(&values), ["a a", "b"]);
+        assert_eq!(strings(optional_values[0].as_ref()), [""]);
+        assert!(optional_values[1].is_none());
+
+        let validate_input = |input: &str| {
+            error_message(validate_key_values(
+                "tt_difference",
+                parse(input),
+                &["lhs"],
+                &["extra"],
+            ))
+        };
+        assert!(validate_input("").contains("did not receive caller's tt bundle"));
+        assert!(validate_input("c").contains("named 'lhs' but did not receive it"));
+        assert!(validate_input("c rhs = [{ a }]").contains("keys to be named"));
+        assert!(validate_input("c lhs").contains("did not receive it"));
+        assert!(validate_input("c lhs : [{ a }]").contains("separated by a '='"));
+        assert!(validate_input("c lhs =").contains("received no value"));
+        assert!(validate_input("c lhs = [ ]").contains("the '{..}' was not given"));
+        assert!(validate_input("c lhs = [{ a } b]").contains("additional tokens"));
+        assert!(validate_input("c lhs = { a }").contains("expects delimiter 'Bracket'"));
+        assert!(validate_input("c lhs = [{ a }] lhs = [{ a }]").contains("only one"));
+    }
+
+    ///
+    /// Tests that `expect_group` only unwraps groups with the given delimiter.
+    ///
+    #[test]
+    fn test_expect_group() {
+        let group = |s: &str| parse(s).into_iter().next().unwrap();
+        let contents = value(expect_group("tt_equal", group("[a b]"), Delimiter::Bracket));
+        assert_eq!(contents.to_string(), "a b");
+        assert!(
+            error_message(expect_group("tt_equal", group("(a b)"), Delimiter::Bracket))
+                .contains("expects delimiter 'Bracket' but got 'Parenthesis'")
+        );
+        assert!(
+            error_message(expect_group("tt_equal", group("a"), Delimiter::Bracket))
+                .contains("expects a group of tokens inside Bracket")
+        );
+    }
+
+    ///
+    /// Tests that `parse_mode` enables the mode an alias stands for.
+    ///
+    #[test]
+    fn test_parse_mode_alias() {
+        let mode = value(parse_mode("tt_equal", parse("alpha_rename_generics")));
+        assert!(mode.ignore_impl_generics_names);
+        assert!(!MODES.contains(&"alpha_rename_generics"));
+    }
+
+    ///
+    /// Tests that `expand_tt_bytes_equal_int` requires the bytes of the array to be separated
+    /// by single commas, allowing only a trailing comma.
+    ///
+    #[test]
+    fn test_expand_tt_bytes_equal_int_separators() {
+        let expand = |array: &str| {
+            expand_tt_bytes_equal_int(parse(&format!(
+                "c input = [{{ 0x5678 {} }}] endian = [{{ le }}]",
+                array
+            )))
+        };
+        assert!(value(expand("[0x78, 0x56]")).to_string().contains("true"));
+        assert!(value(expand("[0x78, 0x56,]")).to_string().contains("true"));
+        assert!(error_message(expand("[0x78 0x56]"))
+            .contains("separated by commas but received '0x56'"));
+        assert!(
+            error_message(expand("[0x78,, 0x56]")).contains("only byte literals but received ','")
+        );
+        assert!(
+            error_message(expand("[, 0x78, 0x56]")).contains("only byte literals but received ','")
+        );
     }
 }
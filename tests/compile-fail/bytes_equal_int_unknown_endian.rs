@@ -0,0 +1,24 @@
+#![allow(unused_macros)]
+
+use tt_call::tt_call;
+use tt_equal::tt_bytes_equal_int;
+
+macro_rules! bool_const {
+    {
+        name = [{ $name:ident }]
+        is_equal = [{ $is_equal:tt }]
+    } => {
+        const $name: bool = $is_equal;
+    }
+}
+
+tt_call! {
+    macro = [{ tt_bytes_equal_int }]
+    input = [{ 0x1234 [0x34, 0x12] }]
+    endian = [{ middle }]
+    ~~> bool_const! {
+        name = [{ UNKNOWN_ENDIAN }]
+    }
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+#![allow(unused_macros)]
+
+use tt_call::tt_call;
+use tt_equal::tt_derives_contains;
+
+macro_rules! bool_const {
+    {
+        name = [{ $name:ident }]
+        contains = [{ $contains:tt }]
+    } => {
+        const $name: bool = $contains;
+    }
+}
+
+tt_call! {
+    macro = [{ tt_derives_contains }]
+    input = [{ #[cfg(test)] }]
+    required = [{ Debug }]
+    ~~> bool_const! {
+        name = [{ NOT_DERIVE }]
+    }
+}
+
+fn main() {}
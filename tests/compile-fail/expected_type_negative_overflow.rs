@@ -0,0 +1,25 @@
+#![allow(unused_macros)]
+
+use tt_call::tt_call;
+use tt_equal::tt_equal;
+
+macro_rules! bool_const {
+    {
+        name = [{ $name:ident }]
+        is_equal = [{ $is_equal:tt }]
+    } => {
+        const $name: bool = $is_equal;
+    }
+}
+
+tt_call! {
+    macro = [{ tt_equal }]
+    input = [{ -129 0 }]
+    mode = [{ signed_numbers }]
+    expected_type = [{ i8 }]
+    ~~> bool_const! {
+        name = [{ OUT_OF_RANGE }]
+    }
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+#![allow(unused_macros)]
+
+use tt_call::tt_call;
+use tt_equal::tt_equal;
+
+macro_rules! bool_const {
+    {
+        name = [{ $name:ident }]
+        is_equal = [{ $is_equal:tt }]
+    } => {
+        const $name: bool = $is_equal;
+    }
+}
+
+tt_call! {
+    macro = [{ tt_equal }]
+    input = [{ 1 1 }]
+    expected_type = [{ usize }]
+    ~~> bool_const! {
+        name = [{ UNKNOWN_TYPE }]
+    }
+}
+
+fn main() {}
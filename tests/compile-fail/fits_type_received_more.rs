@@ -0,0 +1,23 @@
+#![allow(unused_macros)]
+
+use tt_call::tt_call;
+use tt_equal::tt_fits_type;
+
+macro_rules! bool_const {
+    {
+        name = [{ $name:ident }]
+        fits = [{ $fits:tt }]
+    } => {
+        const $name: bool = $fits;
+    }
+}
+
+tt_call! {
+    macro = [{ tt_fits_type }]
+    input = [{ 1 u8 u16 }]
+    ~~> bool_const! {
+        name = [{ RECEIVED_MORE }]
+    }
+}
+
+fn main() {}
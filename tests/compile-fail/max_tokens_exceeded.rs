@@ -0,0 +1,25 @@
+#![allow(unused_macros)]
+
+use tt_call::tt_call;
+use tt_equal::tt_equal;
+
+macro_rules! bool_const {
+    {
+        name = [{ $name:ident }]
+        is_equal = [{ $is_equal:tt }]
+    } => {
+        const $name: bool = $is_equal;
+    }
+}
+
+// Each side is 3 nested groups around an identifier, i.e. 4 tokens, for a total of 8.
+tt_call! {
+    macro = [{ tt_equal }]
+    input = [{ (((a))) (((a))) }]
+    max_tokens = [{ 7 }]
+    ~~> bool_const! {
+        name = [{ ABOVE_MAX_TOKENS }]
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+#![allow(unused_macros)]
+
+use tt_call::tt_call;
+use tt_equal::tt_equal;
+
+macro_rules! bool_const {
+    {
+        name = [{ $name:ident }]
+        is_equal = [{ $is_equal:tt }]
+    } => {
+        const $name: bool = $is_equal;
+    }
+}
+
+tt_call! {
+    macro = [{ tt_equal }]
+    mode = [{ semantic }]
+    ~~> bool_const! {
+        name = [{ MISSING_INPUT }]
+    }
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+#![allow(unused_macros)]
+
+use tt_call::tt_call;
+use tt_equal::tt_equal;
+
+macro_rules! bool_const {
+    {
+        name = [{ $name:ident }]
+        is_equal = [{ $is_equal:tt }]
+    } => {
+        const $name: bool = $is_equal;
+    }
+}
+
+macro_rules! compare_before_semicolon {
+    {
+        $lhs:tt $rhs:tt;
+    } => {
+        tt_call! {
+            macro = [{ tt_equal }]
+            input = [{ $lhs $rhs }]
+            ~~> bool_const! {
+                name = [{ TRAILING_JOINT_PUNCT }]
+            }
+        }
+    }
+}
+
+compare_before_semicolon!(+ +;);
+
+fn main() {}
@@ -0,0 +1,15 @@
+//!
+//! Tests that malformed input results in the expected compile errors.
+//!
+//! Each file in 'tests/compile-fail' must fail to compile with the error in
+//! the accompanying '.stderr' file.
+//!
+
+///
+/// Tests all the cases in 'tests/compile-fail'.
+///
+#[test]
+fn test_compile_fail() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/compile-fail/*.rs");
+}
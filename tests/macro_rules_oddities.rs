@@ -15,6 +15,7 @@
 //! Even if the implementation of macros changes, and the above is no longer the case,
 //! this test will ensure we notice of our solution stops working.
 //!
+#![allow(clippy::assertions_on_constants)]
 
 macro_rules! duplicate_for_bool{
 
@@ -1,6 +1,7 @@
 //#![feature(trace_macros)] //trace_macros!(true);
+#![allow(clippy::assertions_on_constants)]
 
-use tt_call::tt_if;
+use tt_call::{tt_call, tt_if};
 use tt_equal::tt_equal;
 
 ///
@@ -46,3 +47,1962 @@ fn test_tt_equal_invocations() {
     assert!(DOUBLE_DOUBLE_COLON);
     assert!(!INCLUSIVE_RANGE_DOUBLE_COLON);
 }
+
+invoke_tt_equal!(MATCHER_SAME_FRAGMENT ($x:expr) ($x:expr));
+invoke_tt_equal!(MATCHER_SPACED_FRAGMENT ($x:expr) ($x : expr));
+invoke_tt_equal!(MATCHER_DIFFERENT_FRAGMENT ($x:expr) ($x:ty));
+invoke_tt_equal!(MATCHER_REPETITION ($($x:tt),*) ($($x:tt),*));
+invoke_tt_equal!(MATCHER_DIFFERENT_REPETITION ($($x:tt),*) ($($x:tt);*));
+
+///
+/// Tests that `macro_rules!` matchers, with their `$` and fragment specifiers, can be compared.
+///
+#[test]
+fn test_macro_rules_matchers() {
+    assert!(MATCHER_SAME_FRAGMENT);
+    assert!(MATCHER_SPACED_FRAGMENT);
+    assert!(!MATCHER_DIFFERENT_FRAGMENT);
+    assert!(MATCHER_REPETITION);
+    assert!(!MATCHER_DIFFERENT_REPETITION);
+}
+
+invoke_tt_equal!(AWAIT_SPACED {foo().await} {foo() . await});
+invoke_tt_equal!(AWAIT_CHAINED {foo().await?.bar().await} {foo() .await ?. bar() .await});
+invoke_tt_equal!(AWAIT_DIFFERENT_CALLEE {foo().await} {bar().await});
+invoke_tt_equal!(AWAIT_FIELD {foo().await} {foo().awaits});
+
+///
+/// Tests that `.await` expressions compare structurally, as a `.` followed by the `await`
+/// keyword, regardless of spacing.
+///
+#[test]
+fn test_await() {
+    assert!(AWAIT_SPACED);
+    assert!(AWAIT_CHAINED);
+    assert!(!AWAIT_DIFFERENT_CALLEE);
+    assert!(!AWAIT_FIELD);
+}
+
+invoke_tt_equal!(MACRO_2_SPACED
+    {macro foo($x:expr) { $x + 1 }} {macro foo ( $ x : expr ) {$x+1}});
+invoke_tt_equal!(MACRO_2_UNTYPED {pub macro foo($x) { $x }} {pub macro foo($ x) { $ x }});
+invoke_tt_equal!(MACRO_2_DIFFERENT_BODY
+    {macro foo($x:expr) { $x + 1 }} {macro foo($x:expr) { $x + 2 }});
+invoke_tt_equal!(MACRO_2_DIFFERENT_NAME {macro foo($x) { $x }} {macro bar($x) { $x }});
+
+///
+/// Tests that definitions of the unstable declarative macros 2.0 can be compared.
+///
+#[test]
+fn test_macros_2() {
+    assert!(MACRO_2_SPACED);
+    assert!(MACRO_2_UNTYPED);
+    assert!(!MACRO_2_DIFFERENT_BODY);
+    assert!(!MACRO_2_DIFFERENT_NAME);
+}
+
+invoke_tt_equal!(UNITS () ());
+invoke_tt_equal!(UNIT_SPACED_UNIT () ( ));
+invoke_tt_equal!(UNIT_COMMA_TUPLE () (,));
+invoke_tt_equal!(UNIT_EMPTY_BRACKETS () []);
+invoke_tt_equal!(UNIT_NON_EMPTY () (a));
+
+///
+/// Tests that empty groups compare equal regardless of whitespace,
+/// but not to non-empty groups or groups with other delimiters.
+///
+#[test]
+fn test_empty_groups() {
+    assert!(UNITS);
+    assert!(UNIT_SPACED_UNIT);
+    assert!(!UNIT_COMMA_TUPLE);
+    assert!(!UNIT_EMPTY_BRACKETS);
+    assert!(!UNIT_NON_EMPTY);
+}
+
+invoke_tt_equal!(GROUP_INNER_SPACES (Two tokens) ( Two tokens ));
+invoke_tt_equal!(GROUP_COMMA_SPACES (a , b) (a,b));
+invoke_tt_equal!(GROUP_DIFFERENT_DELIMITERS (a) [a]);
+invoke_tt_equal!(GROUP_DIFFERENT_LENGTHS (a b) (a b c));
+invoke_tt_equal!(NESTED_GROUP_SPACES ({ a , [b ; c] }) ({a,[b;c]}));
+invoke_tt_equal!(NESTED_GROUP_DIFFERENT_DELIMITERS ({ a, [b] }) ({ a, (b) }));
+invoke_tt_equal!(NESTED_GROUP_DIFFERENT_TOKENS ({ a, [b] }) ({ a, [c] }));
+invoke_tt_equal!(NESTED_JOINT_PUNCT ((a += b)) ((a+=b)));
+invoke_tt_equal!(NESTED_SPLIT_PUNCT ((a += b)) ((a + = b)));
+invoke_tt_equal!(NESTED_LIFETIMES (&'a str) (& 'a str));
+invoke_tt_equal!(NESTED_DIFFERENT_LIFETIMES (&'a str) (&'b str));
+
+///
+/// Tests that groups are compared by delimiter and contents, ignoring whitespace.
+///
+#[test]
+fn test_group_structure() {
+    assert!(GROUP_INNER_SPACES);
+    assert!(GROUP_COMMA_SPACES);
+    assert!(!GROUP_DIFFERENT_DELIMITERS);
+    assert!(!GROUP_DIFFERENT_LENGTHS);
+    assert!(NESTED_GROUP_SPACES);
+    assert!(!NESTED_GROUP_DIFFERENT_DELIMITERS);
+    assert!(!NESTED_GROUP_DIFFERENT_TOKENS);
+    assert!(NESTED_JOINT_PUNCT);
+    assert!(!NESTED_SPLIT_PUNCT);
+    assert!(NESTED_LIFETIMES);
+    assert!(!NESTED_DIFFERENT_LIFETIMES);
+}
+
+///
+/// For each given two-character operator, produces a module testing that the operator
+/// is equal to itself, but not to either of its constituent characters nor to its
+/// constituent characters spaced apart.
+///
+macro_rules! test_two_char_operators {
+    {
+        $( $name:ident $op:tt $first:tt $second:tt ; )*
+    } => {
+        $(
+            mod $name {
+                use super::*;
+
+                invoke_tt_equal!(ITSELF $op $op);
+                invoke_tt_equal!(FIRST $op $first);
+                invoke_tt_equal!(SECOND $op $second);
+                invoke_tt_equal!(SPACED ($op) ($first $second));
+
+                ///
+                /// Tests that the operator is compared as a single unit.
+                ///
+                #[test]
+                fn test_operator() {
+                    assert!(ITSELF);
+                    assert!(!FIRST);
+                    assert!(!SECOND);
+                    assert!(!SPACED);
+                }
+            }
+        )*
+    }
+}
+
+test_two_char_operators! {
+    not_equal != ! = ;
+    rem_assign %= % = ;
+    and_and && & & ;
+    and_assign &= & = ;
+    mul_assign *= * = ;
+    add_assign += + = ;
+    sub_assign -= - = ;
+    r_arrow -> - > ;
+    dot_dot .. . . ;
+    div_assign /= / = ;
+    path_sep :: : : ;
+    shl << < < ;
+    less_equal <= < = ;
+    equal_equal == = = ;
+    fat_arrow => = > ;
+    greater_equal >= > = ;
+    shr >> > > ;
+    xor_assign ^= ^ = ;
+    or_assign |= | = ;
+    or_or || | | ;
+}
+
+///
+/// Produces a bool const with the given name and value.
+/// Used as the return destination of 'tt_equal' when invoked through 'tt_call'.
+///
+macro_rules! bool_const {
+    {
+        name = [{ $name:ident }]
+        is_equal = [{ $is_equal:tt }]
+    } => {
+        const $name: bool = $is_equal;
+    }
+}
+
+///
+/// We use this macro to invoke 'tt_equal' in the given mode and produce a bool const of whether
+/// the two given tokens were equal.
+///
+/// The first argument is the name of the resulting const, the second is the mode,
+/// while the two following arguments are to be compared.
+///
+macro_rules! invoke_tt_equal_in_mode {
+    {
+        $id1:ident [ $($mode:tt)* ] $tt1:tt $tt2:tt
+    } => {
+        tt_call! {
+            macro = [{ tt_equal }]
+            input = [{ $tt1 $tt2 }]
+            mode = [{ $($mode)* }]
+            ~~> bool_const! {
+                name = [{ $id1 }]
+            }
+        }
+    }
+}
+
+invoke_tt_equal!(DECIMAL_UNDERSCORE 1000 1_000);
+invoke_tt_equal_in_mode!(SEMANTIC_DECIMAL_UNDERSCORE [semantic] 1000 1_000);
+invoke_tt_equal_in_mode!(SEMANTIC_DECIMAL_HEX [semantic] 1000 0x3E8);
+invoke_tt_equal_in_mode!(SEMANTIC_BINARY_OCTAL [semantic] 0b1111101000 0o1750);
+invoke_tt_equal_in_mode!(SEMANTIC_DIFFERENT_VALUES [semantic] 1000 0x3E9);
+invoke_tt_equal_in_mode!(SEMANTIC_SUFFIX_UNSUFFIXED [semantic] 1u32 1);
+invoke_tt_equal_in_mode!(SEMANTIC_SAME_SUFFIX [semantic] 1u32 0x1_u32);
+invoke_tt_equal_in_mode!(SEMANTIC_DIFFERENT_SUFFIX [semantic] 1u32 1u64);
+invoke_tt_equal_in_mode!(SEMANTIC_FLOATS [semantic] 1.0 1.0);
+invoke_tt_equal_in_mode!(SEMANTIC_NESTED[semantic](1_000, [2])(0x3E8, [0b10]));
+invoke_tt_equal_in_mode!(SEMANTIC_OVERFLOWING [semantic]
+    340282366920938463463374607431768211456 340282366920938463463374607431768211457);
+invoke_tt_equal_in_mode!(SEMANTIC_OVERFLOWING_SAME [semantic]
+    340282366920938463463374607431768211456 340282366920938463463374607431768211456);
+invoke_tt_equal_in_mode!(NO_MODE [] 1000 1_000);
+invoke_tt_equal!(EXPONENT_PLUS_DECIMAL 1e+3 1000.0);
+invoke_tt_equal_in_mode!(SEMANTIC_EXPONENT_PLUS [semantic] 1e+3 1000.0);
+invoke_tt_equal_in_mode!(SEMANTIC_EXPONENT_UPPER_PLUS [semantic] 1E+3 1000.0);
+invoke_tt_equal_in_mode!(SEMANTIC_EXPONENT_UNSIGNED [semantic] 1e3 1_000.0);
+invoke_tt_equal_in_mode!(SEMANTIC_EXPONENT_MINUS [semantic] 1e-3 0.001);
+invoke_tt_equal_in_mode!(SEMANTIC_EXPONENT_UPPER_MINUS [semantic] 1E-3 0.001);
+invoke_tt_equal_in_mode!(SEMANTIC_EXPONENT_SUFFIX [semantic] 2.5e-3f32 0.0025f32);
+invoke_tt_equal_in_mode!(SEMANTIC_EXPONENT_DIFFERENT_SUFFIX [semantic] 1e3f32 1000.0);
+invoke_tt_equal_in_mode!(SEMANTIC_EXPONENT_DIFFERENT_SIGN [semantic] 1e+3 1e-3);
+invoke_tt_equal_in_mode!(SEMANTIC_FLOAT_INTEGER [semantic] 1e3 1000);
+
+///
+/// Tests that the semantic mode compares integer literals by value and suffix.
+///
+#[test]
+fn test_semantic_mode() {
+    assert!(!DECIMAL_UNDERSCORE);
+    assert!(SEMANTIC_DECIMAL_UNDERSCORE);
+    assert!(SEMANTIC_DECIMAL_HEX);
+    assert!(SEMANTIC_BINARY_OCTAL);
+    assert!(!SEMANTIC_DIFFERENT_VALUES);
+    assert!(!SEMANTIC_SUFFIX_UNSUFFIXED);
+    assert!(SEMANTIC_SAME_SUFFIX);
+    assert!(!SEMANTIC_DIFFERENT_SUFFIX);
+    assert!(SEMANTIC_FLOATS);
+    assert!(SEMANTIC_NESTED);
+    assert!(!SEMANTIC_OVERFLOWING);
+    assert!(SEMANTIC_OVERFLOWING_SAME);
+    assert!(!NO_MODE);
+}
+
+///
+/// Tests that the semantic mode compares float literals by value, including signed exponents,
+/// which are part of the float literal token.
+///
+#[test]
+fn test_semantic_mode_exponents() {
+    assert!(!EXPONENT_PLUS_DECIMAL);
+    assert!(SEMANTIC_EXPONENT_PLUS);
+    assert!(SEMANTIC_EXPONENT_UPPER_PLUS);
+    assert!(SEMANTIC_EXPONENT_UNSIGNED);
+    assert!(SEMANTIC_EXPONENT_MINUS);
+    assert!(SEMANTIC_EXPONENT_UPPER_MINUS);
+    assert!(SEMANTIC_EXPONENT_SUFFIX);
+    assert!(!SEMANTIC_EXPONENT_DIFFERENT_SUFFIX);
+    assert!(!SEMANTIC_EXPONENT_DIFFERENT_SIGN);
+    assert!(!SEMANTIC_FLOAT_INTEGER);
+}
+
+invoke_tt_equal!(TRAILING_DOT 1. 1.0);
+invoke_tt_equal_in_mode!(SEMANTIC_TRAILING_DOT [semantic] 1. 1.0);
+invoke_tt_equal_in_mode!(SEMANTIC_TRAILING_DOT_EXPONENT [semantic] 10. 1e1);
+invoke_tt_equal_in_mode!(SEMANTIC_TRAILING_DOT_NESTED[semantic](1. + x)(1.0 + x));
+invoke_tt_equal_in_mode!(SEMANTIC_TRAILING_DOT_DIFFERENT [semantic] 1. 1.5);
+invoke_tt_equal_in_mode!(SEMANTIC_TRAILING_DOT_INTEGER [semantic] 1. 1);
+
+///
+/// Tests that the semantic mode compares float literals with a trailing dot by value.
+///
+#[test]
+fn test_semantic_mode_trailing_dot() {
+    assert!(!TRAILING_DOT);
+    assert!(SEMANTIC_TRAILING_DOT);
+    assert!(SEMANTIC_TRAILING_DOT_EXPONENT);
+    assert!(SEMANTIC_TRAILING_DOT_NESTED);
+    assert!(!SEMANTIC_TRAILING_DOT_DIFFERENT);
+    assert!(!SEMANTIC_TRAILING_DOT_INTEGER);
+}
+
+invoke_tt_equal!(STRING_ESCAPED "\x41" "A");
+invoke_tt_equal_in_mode!(SEMANTIC_STRING_ESCAPED [semantic] "\x41" "A");
+invoke_tt_equal_in_mode!(SEMANTIC_STRING_UNICODE_ESCAPED [semantic] "\u{e9}t\u{e9}" "été");
+invoke_tt_equal_in_mode!(SEMANTIC_STRING_UNICODE_UNDERSCORES [semantic] "\u{1_F600}" "😀");
+invoke_tt_equal_in_mode!(SEMANTIC_STRING_RAW [semantic] r"a\b" "a\\b");
+invoke_tt_equal_in_mode!(SEMANTIC_STRING_RAW_HASHES [semantic] r#"say "hi""# "say \"hi\"");
+invoke_tt_equal_in_mode!(SEMANTIC_STRING_DIFFERENT [semantic] "\x41" "B");
+invoke_tt_equal_in_mode!(SEMANTIC_STRING_BYTE_STRING [semantic] "A" b"A");
+
+///
+/// Tests that the semantic mode compares string literals by the strings they represent.
+///
+#[test]
+fn test_semantic_mode_strings() {
+    assert!(!STRING_ESCAPED);
+    assert!(SEMANTIC_STRING_ESCAPED);
+    assert!(SEMANTIC_STRING_UNICODE_ESCAPED);
+    assert!(SEMANTIC_STRING_UNICODE_UNDERSCORES);
+    assert!(SEMANTIC_STRING_RAW);
+    assert!(SEMANTIC_STRING_RAW_HASHES);
+    assert!(!SEMANTIC_STRING_DIFFERENT);
+    assert!(!SEMANTIC_STRING_BYTE_STRING);
+}
+
+invoke_tt_equal!(GLOB_EXPLICIT (use foo::*;) (use foo::{a};));
+invoke_tt_equal_in_mode!(GLOB_MATCHES_EXPLICIT [glob_matches_explicit] (use foo::*;) (use foo::{a};));
+invoke_tt_equal_in_mode!(EXPLICIT_MATCHES_GLOB [glob_matches_explicit] (use foo::{a, b};) (use foo::*;));
+invoke_tt_equal_in_mode!(GLOB_MATCHES_EXPLICIT_SPACED [glob_matches_explicit] (use foo:: * ;) (use foo::{a};));
+invoke_tt_equal_in_mode!(GLOB_MATCHES_EXPLICIT_NESTED [glob_matches_explicit] {use foo::{*, bar::*};} {use foo::{*, bar::{a}};});
+invoke_tt_equal_in_mode!(GLOB_DIFFERENT_PREFIX [glob_matches_explicit] (use foo::*;) (use bar::{a};));
+invoke_tt_equal_in_mode!(GLOB_NOT_AFTER_SEPARATOR [glob_matches_explicit] (a * b) (a {} b));
+invoke_tt_equal_in_mode!(GLOB_NOT_BRACE [glob_matches_explicit] (use foo::*;) (use foo::(a);));
+
+///
+/// Tests that the glob_matches_explicit mode matches glob imports against any explicit import
+/// list with the same prefix.
+///
+#[test]
+fn test_glob_matches_explicit_mode() {
+    assert!(!GLOB_EXPLICIT);
+    assert!(GLOB_MATCHES_EXPLICIT);
+    assert!(EXPLICIT_MATCHES_GLOB);
+    assert!(GLOB_MATCHES_EXPLICIT_SPACED);
+    assert!(GLOB_MATCHES_EXPLICIT_NESTED);
+    assert!(!GLOB_DIFFERENT_PREFIX);
+    assert!(!GLOB_NOT_AFTER_SEPARATOR);
+    assert!(!GLOB_NOT_BRACE);
+}
+
+// Each side is 8 nested groups around an identifier, i.e. 9 tokens, for a total of 18.
+tt_call! {
+    macro = [{ tt_equal }]
+    input = [{ ((((((((a)))))))) ((((((((a)))))))) }]
+    max_tokens = [{ 18 }]
+    ~~> bool_const! {
+        name = [{ NESTED_AT_MAX_TOKENS }]
+    }
+}
+tt_call! {
+    macro = [{ tt_equal }]
+    input = [{ ((((((((a)))))))) ((((((((b)))))))) }]
+    max_tokens = [{ 18 }]
+    ~~> bool_const! {
+        name = [{ NESTED_UNEQUAL_AT_MAX_TOKENS }]
+    }
+}
+
+///
+/// Tests that input with exactly the maximum number of tokens is still compared.
+///
+#[test]
+fn test_max_tokens() {
+    assert!(NESTED_AT_MAX_TOKENS);
+    assert!(!NESTED_UNEQUAL_AT_MAX_TOKENS);
+}
+
+invoke_tt_equal!(INLINE_BOUND_WHERE_CLAUSE {fn f<T: Clone>() {}} {fn f<T>() where T: Clone {}});
+invoke_tt_equal_in_mode!(NORMALIZED_INLINE_BOUND_WHERE_CLAUSE [normalize_bounds_position]
+    {fn f<T: Clone>() {}} {fn f<T>() where T: Clone {}});
+invoke_tt_equal_in_mode!(NORMALIZED_MIXED_BOUNDS [normalize_bounds_position]
+    {fn f<'a, T: Clone + Debug, U>(t: &'a T) -> U where U: Default;}
+    {fn f<'a, T: Debug, U: Default>(t: &'a T) -> U where T: Clone,;});
+invoke_tt_equal_in_mode!(NORMALIZED_NESTED_GENERICS [normalize_bounds_position]
+    {impl X { fn f<T: Into<Vec<u8>>, F: Fn(T) -> u8>() {} }}
+    {impl X { fn f<T, F>() where F: Fn(T) -> u8, T: Into<Vec<u8>> {} }});
+invoke_tt_equal_in_mode!(NORMALIZED_DIFFERENT_BOUNDS [normalize_bounds_position]
+    {fn f<T: Clone>() {}} {fn f<T>() where T: Copy {}});
+invoke_tt_equal_in_mode!(NORMALIZED_DIFFERENT_BOUNDED [normalize_bounds_position]
+    {fn f<T: Clone, U>() {}} {fn f<T, U>() where U: Clone {}});
+
+///
+/// Tests that the normalize_bounds_position mode ignores whether bounds are given inline
+/// or in the where clause.
+///
+#[test]
+fn test_normalize_bounds_position_mode() {
+    assert!(!INLINE_BOUND_WHERE_CLAUSE);
+    assert!(NORMALIZED_INLINE_BOUND_WHERE_CLAUSE);
+    assert!(NORMALIZED_MIXED_BOUNDS);
+    assert!(NORMALIZED_NESTED_GENERICS);
+    assert!(!NORMALIZED_DIFFERENT_BOUNDS);
+    assert!(!NORMALIZED_DIFFERENT_BOUNDED);
+}
+
+invoke_tt_equal!(DOC_LINES_DOC_STRING
+    {
+        /// First line
+        /// Second line
+        struct S;
+    }
+    {
+        #[doc = " First line\n Second line"]
+        struct S;
+    }
+);
+invoke_tt_equal_in_mode!(CONCATENATED_DOC_LINES_DOC_STRING [concat_doc_attrs]
+    {
+        /// First line
+        /// Second line
+        struct S;
+    }
+    {
+        #[doc = " First line\n Second line"]
+        struct S;
+    }
+);
+invoke_tt_equal_in_mode!(CONCATENATED_DOC_ATTRS_RAW_STRING [concat_doc_attrs]
+    {
+        #[doc = "First \"line\""]
+        #[doc = "Second line"]
+        struct S;
+    }
+    {
+        #[doc = r#"First "line"
+Second line"#]
+        struct S;
+    }
+);
+invoke_tt_equal_in_mode!(CONCATENATED_DOC_ATTRS_UNICODE_UNDERSCORES [concat_doc_attrs]
+    {
+        #[doc = "\u{1_F600}"]
+        struct S;
+    }
+    {
+        #[doc = "😀"]
+        struct S;
+    }
+);
+invoke_tt_equal_in_mode!(CONCATENATED_DOC_LINES_DIFFERENT [concat_doc_attrs]
+    {
+        /// First line
+        /// Second line
+        struct S;
+    }
+    {
+        #[doc = " First line\n Other line"]
+        struct S;
+    }
+);
+invoke_tt_equal_in_mode!(CONCATENATED_DOC_LINES_SEPARATED [concat_doc_attrs]
+    {
+        /// First line
+        #[derive(Debug)]
+        /// Second line
+        struct S;
+    }
+    {
+        #[doc = " First line\n Second line"]
+        #[derive(Debug)]
+        struct S;
+    }
+);
+
+///
+/// Tests that the concat_doc_attrs mode compares consecutive doc attributes as one.
+///
+#[test]
+fn test_concat_doc_attrs_mode() {
+    assert!(!DOC_LINES_DOC_STRING);
+    assert!(CONCATENATED_DOC_LINES_DOC_STRING);
+    assert!(CONCATENATED_DOC_ATTRS_RAW_STRING);
+    assert!(CONCATENATED_DOC_ATTRS_UNICODE_UNDERSCORES);
+    assert!(!CONCATENATED_DOC_LINES_DIFFERENT);
+    assert!(!CONCATENATED_DOC_LINES_SEPARATED);
+}
+
+///
+/// We use this macro to invoke 'tt_equal' on the given input, which need not be two token trees
+/// as seen by 'macro_rules', and produce a bool const of whether the two sides were equal.
+///
+macro_rules! invoke_tt_equal_on_input {
+    {
+        $id1:ident [ $($input:tt)* ]
+    } => {
+        tt_call! {
+            macro = [{ tt_equal }]
+            input = [{ $($input)* }]
+            ~~> bool_const! {
+                name = [{ $id1 }]
+            }
+        }
+    }
+}
+
+///
+/// We use this macro to invoke 'tt_equal' in the given mode on the given input, which need not
+/// be two token trees as seen by 'macro_rules', and produce a bool const of whether the two
+/// sides were equal.
+///
+macro_rules! invoke_tt_equal_in_mode_on_input {
+    {
+        $id1:ident [ $($mode:tt)* ] [ $($input:tt)* ]
+    } => {
+        tt_call! {
+            macro = [{ tt_equal }]
+            input = [{ $($input)* }]
+            mode = [{ $($mode)* }]
+            ~~> bool_const! {
+                name = [{ $id1 }]
+            }
+        }
+    }
+}
+
+invoke_tt_equal_on_input!(MINUS_ONE [- 1]);
+invoke_tt_equal_on_input!(MINUS_ONE_UNSPACED [-1]);
+invoke_tt_equal_on_input!(MINUS_MINUS [- -]);
+invoke_tt_equal_in_mode_on_input!(NEGATIVE_NEGATIVE [signed_numbers] [-1 -1]);
+invoke_tt_equal_in_mode_on_input!(NEGATIVE_SPACED_NEGATIVE [signed_numbers] [-1 - 1]);
+invoke_tt_equal_in_mode_on_input!(NEGATIVE_POSITIVE [signed_numbers] [-1 1]);
+invoke_tt_equal_in_mode_on_input!(NEGATIVE_PLUS [signed_numbers] [-1 +1]);
+invoke_tt_equal_in_mode_on_input!(NEGATIVE_DIFFERENT [signed_numbers] [-1 -2]);
+invoke_tt_equal_in_mode_on_input!(NEGATIVE_FLOAT [signed_numbers] [-1.5 - 1.5]);
+
+///
+/// Tests that a sign and the number following it are two token trees by default, while the
+/// signed_numbers mode compares them as a single token tree.
+///
+#[test]
+fn test_signed_numbers_mode() {
+    assert!(!MINUS_ONE);
+    assert!(!MINUS_ONE_UNSPACED);
+    assert!(MINUS_MINUS);
+    assert!(NEGATIVE_NEGATIVE);
+    assert!(NEGATIVE_SPACED_NEGATIVE);
+    assert!(!NEGATIVE_POSITIVE);
+    assert!(!NEGATIVE_PLUS);
+    assert!(!NEGATIVE_DIFFERENT);
+    assert!(NEGATIVE_FLOAT);
+}
+
+invoke_tt_equal_in_mode!(PLUS_SEMANTIC [semantic] (+5) (5));
+invoke_tt_equal_in_mode_on_input!(SIGNED_PLUS [signed_numbers] [+5 5]);
+invoke_tt_equal_in_mode_on_input!(SEMANTIC_SIGNED_PLUS [semantic signed_numbers] [+5 5]);
+invoke_tt_equal_in_mode_on_input!(SEMANTIC_SIGNED_PLUS_HEX [semantic signed_numbers] [+0x5 5]);
+invoke_tt_equal_in_mode_on_input!(SEMANTIC_SIGNED_MINUS_HEX [semantic signed_numbers] [-5 -0x5]);
+invoke_tt_equal_in_mode_on_input!(SEMANTIC_SIGNED_MINUS_PLUS [semantic signed_numbers] [-5 +5]);
+invoke_tt_equal_in_mode!(SEMANTIC_SIGNED_PLUS_GROUP [semantic signed_numbers] (+5) (5));
+invoke_tt_equal_in_mode!(SEMANTIC_SIGNED_PLUS_ARGUMENT [semantic signed_numbers]
+    (f(a, +5)) (f(a, 5)));
+invoke_tt_equal_in_mode!(SEMANTIC_SIGNED_PLUS_BINARY [semantic signed_numbers] {a + 5} {a 5});
+
+///
+/// Tests that the semantic and signed_numbers modes together ignore a unary `+` before
+/// a number.
+///
+#[test]
+fn test_semantic_signed_numbers_modes() {
+    assert!(!PLUS_SEMANTIC);
+    assert!(!SIGNED_PLUS);
+    assert!(SEMANTIC_SIGNED_PLUS);
+    assert!(SEMANTIC_SIGNED_PLUS_HEX);
+    assert!(SEMANTIC_SIGNED_MINUS_HEX);
+    assert!(!SEMANTIC_SIGNED_MINUS_PLUS);
+    assert!(SEMANTIC_SIGNED_PLUS_GROUP);
+    assert!(SEMANTIC_SIGNED_PLUS_ARGUMENT);
+    assert!(!SEMANTIC_SIGNED_PLUS_BINARY);
+}
+
+invoke_tt_equal!(DIFFERENT_CASE Foo foo);
+invoke_tt_equal_in_mode!(CASE_INSENSITIVE_SINGLE_LETTER [case_insensitive] A a);
+invoke_tt_equal_in_mode!(CASE_INSENSITIVE_MIXED_CASE [case_insensitive] FooBar fOObAR);
+invoke_tt_equal_in_mode!(CASE_INSENSITIVE_NESTED [case_insensitive] (Foo::Bar<'A>) (foo::BAR<'a>));
+invoke_tt_equal_in_mode!(CASE_INSENSITIVE_DIFFERENT [case_insensitive] Foo Bar);
+invoke_tt_equal_in_mode!(CASE_INSENSITIVE_STRINGS [case_insensitive] "A" "a");
+invoke_tt_equal_in_mode!(CASE_INSENSITIVE_CHARS [case_insensitive] 'A' 'a');
+invoke_tt_equal_in_mode!(CASE_INSENSITIVE_HEX_LITERALS [case_insensitive] 0xFF 0xff);
+
+///
+/// Tests that the case_insensitive mode only ignores the case of identifiers.
+///
+#[test]
+fn test_case_insensitive_mode() {
+    assert!(!DIFFERENT_CASE);
+    assert!(CASE_INSENSITIVE_SINGLE_LETTER);
+    assert!(CASE_INSENSITIVE_MIXED_CASE);
+    assert!(CASE_INSENSITIVE_NESTED);
+    assert!(!CASE_INSENSITIVE_DIFFERENT);
+    assert!(!CASE_INSENSITIVE_STRINGS);
+    assert!(!CASE_INSENSITIVE_CHARS);
+    assert!(!CASE_INSENSITIVE_HEX_LITERALS);
+}
+
+invoke_tt_equal!(CONST_FN_FN {const fn f() {}} {fn f() {}});
+invoke_tt_equal_in_mode!(IGNORED_CONST_FN [ignore_const_fn] {const fn f() {}} {fn f() {}});
+invoke_tt_equal_in_mode!(IGNORED_CONST_UNSAFE_FN [ignore_const_fn]
+    {pub const unsafe fn f() {}} {pub unsafe fn f() {}});
+invoke_tt_equal_in_mode!(IGNORED_CONST_FN_NESTED [ignore_const_fn]
+    {impl X { const fn f() {} }} {impl X { fn f() {} }});
+invoke_tt_equal_in_mode!(IGNORED_CONST_FN_CONST_ITEM [ignore_const_fn]
+    {const X: u8 = 0;} {X: u8 = 0;});
+invoke_tt_equal_in_mode!(IGNORED_CONST_FN_CONST_GENERIC [ignore_const_fn]
+    {fn f<const N: usize>() {}} {fn f<N: usize>() {}});
+invoke_tt_equal_in_mode!(IGNORED_CONST_FN_DIFFERENT [ignore_const_fn]
+    {const fn f() {}} {fn g() {}});
+
+///
+/// Tests that the ignore_const_fn mode only ignores the `const` of `const fn`.
+///
+#[test]
+fn test_ignore_const_fn_mode() {
+    assert!(!CONST_FN_FN);
+    assert!(IGNORED_CONST_FN);
+    assert!(IGNORED_CONST_UNSAFE_FN);
+    assert!(IGNORED_CONST_FN_NESTED);
+    assert!(!IGNORED_CONST_FN_CONST_ITEM);
+    assert!(!IGNORED_CONST_FN_CONST_GENERIC);
+    assert!(!IGNORED_CONST_FN_DIFFERENT);
+}
+
+invoke_tt_equal!(EXTERN_CRATE_RENAMED {extern crate foo;} {extern crate foo as bar;});
+invoke_tt_equal_in_mode!(NORMALIZED_EXTERN_CRATE_RENAMED [normalize_extern_crate]
+    {extern crate foo;} {extern crate foo as bar;});
+invoke_tt_equal_in_mode!(NORMALIZED_EXTERN_CRATE_BOTH_RENAMED [normalize_extern_crate]
+    {#[macro_use] extern crate foo as _;} {#[macro_use] extern crate foo as baz;});
+invoke_tt_equal_in_mode!(NORMALIZED_EXTERN_CRATE_DIFFERENT [normalize_extern_crate]
+    {extern crate foo;} {extern crate bar;});
+invoke_tt_equal_in_mode!(NORMALIZED_EXTERN_CRATE_RENAMED_TO_OTHER [normalize_extern_crate]
+    {extern crate bar;} {extern crate foo as bar;});
+invoke_tt_equal_in_mode!(NORMALIZED_USE_RENAMED [normalize_extern_crate]
+    {use foo as bar;} {use foo;});
+
+///
+/// Tests that the normalize_extern_crate mode ignores the renames of `extern crate` items.
+///
+#[test]
+fn test_normalize_extern_crate_mode() {
+    assert!(!EXTERN_CRATE_RENAMED);
+    assert!(NORMALIZED_EXTERN_CRATE_RENAMED);
+    assert!(NORMALIZED_EXTERN_CRATE_BOTH_RENAMED);
+    assert!(!NORMALIZED_EXTERN_CRATE_DIFFERENT);
+    assert!(!NORMALIZED_EXTERN_CRATE_RENAMED_TO_OTHER);
+    assert!(!NORMALIZED_USE_RENAMED);
+}
+
+invoke_tt_equal!(STRINGIFIED_SPACING "{ (a, [b]) }" "{(a,[b])}");
+invoke_tt_equal_in_mode!(NORMALIZED_STRINGIFIED_SPACING [normalize_stringify]
+    "{ (a, [b]) }" "{(a,[b])}");
+invoke_tt_equal_in_mode!(NORMALIZED_STRINGIFIED_NESTED [normalize_stringify]
+    "fn f() { if x { g(1, [2, 3]); } }" r#"fn f(){if x{g(1,[2,3]);}}"#);
+invoke_tt_equal_in_mode!(NORMALIZED_STRINGIFIED_DIFFERENT_GROUP [normalize_stringify]
+    "{ (a, [b]) }" "{ (a, (b)) }");
+invoke_tt_equal_in_mode!(NORMALIZED_STRINGIFIED_UNPARSABLE [normalize_stringify]
+    "( a" "( a");
+invoke_tt_equal_in_mode!(NORMALIZED_STRINGIFIED_UNPARSABLE_SPACING [normalize_stringify]
+    "( a" "(a");
+invoke_tt_equal_in_mode!(NORMALIZED_STRINGIFIED_NESTED_STRING [normalize_stringify]
+    "f(\"g( x )\")" "f( \"g(x)\" )");
+invoke_tt_equal_in_mode!(NORMALIZED_STRINGIFIED_SEMANTIC [normalize_stringify semantic]
+    "[0x10]" "[ 16 ]");
+invoke_tt_equal_in_mode!(NORMALIZED_STRINGIFIED_NON_STRING [normalize_stringify]
+    "1" 1);
+
+///
+/// Tests that the normalize_stringify mode compares string literals as the code they contain.
+///
+#[test]
+fn test_normalize_stringify_mode() {
+    assert!(!STRINGIFIED_SPACING);
+    assert!(NORMALIZED_STRINGIFIED_SPACING);
+    assert!(NORMALIZED_STRINGIFIED_NESTED);
+    assert!(!NORMALIZED_STRINGIFIED_DIFFERENT_GROUP);
+    assert!(NORMALIZED_STRINGIFIED_UNPARSABLE);
+    assert!(!NORMALIZED_STRINGIFIED_UNPARSABLE_SPACING);
+    assert!(NORMALIZED_STRINGIFIED_NESTED_STRING);
+    assert!(NORMALIZED_STRINGIFIED_SEMANTIC);
+    assert!(!NORMALIZED_STRINGIFIED_NON_STRING);
+}
+
+invoke_tt_equal!(VARIANT_TUPLE_STRUCT {enum E { Variant(u8, u16) }} {enum E { Variant { a: u8, b: u16 } }});
+invoke_tt_equal_in_mode!(POSITIONAL_VARIANT_TUPLE_STRUCT [variant_fields_positional]
+    {enum E { Variant(u8, u16) }} {enum E { Variant { a: u8, b: u16 } }});
+invoke_tt_equal_in_mode!(POSITIONAL_VARIANT_STRUCT_NAMES [variant_fields_positional]
+    {enum E { Variant { a: u8, b: u16 }, }} {enum E { Variant { x: u8, y: u16 }, }});
+invoke_tt_equal_in_mode!(POSITIONAL_VARIANT_IN_ENUM [variant_fields_positional]
+    {enum E { A(Vec<u8>, #[attr] u16), B }} {enum E { A { v: Vec<u8>, #[attr] w: u16 }, B }});
+invoke_tt_equal_in_mode!(POSITIONAL_VARIANT_GENERIC_ENUM [variant_fields_positional]
+    {pub enum E<T> where T: Clone { A(T) }} {pub enum E<T> where T: Clone { A { t: T } }});
+invoke_tt_equal_in_mode!(POSITIONAL_VARIANT_MISMATCHED_TYPES [variant_fields_positional]
+    {enum E { Variant(u8, u16) }} {enum E { Variant { a: u16, b: u8 } }});
+invoke_tt_equal_in_mode!(POSITIONAL_VARIANT_MISSING_FIELD [variant_fields_positional]
+    {enum E { Variant(u8, u16) }} {enum E { Variant { a: u8 } }});
+invoke_tt_equal_in_mode!(POSITIONAL_VARIANT_STRUCT_ITEM [variant_fields_positional]
+    {struct S(u8);} {struct S { a: u8 }});
+invoke_tt_equal_in_mode!(POSITIONAL_VARIANT_LOOP [variant_fields_positional]
+    {loop {}} {loop ()});
+invoke_tt_equal_in_mode!(POSITIONAL_VARIANT_IMPL [variant_fields_positional]
+    {impl Foo for Bar {}} {impl Foo for Bar ()});
+invoke_tt_equal_in_mode!(POSITIONAL_VARIANT_STRUCT_LITERAL [variant_fields_positional]
+    {let p = Point(1);} {let p = Point { x: 1 };});
+invoke_tt_equal_in_mode!(POSITIONAL_VARIANT_OUTSIDE_ENUM [variant_fields_positional]
+    {Variant(u8, u16)} {Variant { a: u8, b: u16 }});
+
+///
+/// Tests that the variant_fields_positional mode compares the fields of enum variants by
+/// their types' positions, but leaves brace groups outside the bodies of enums as is.
+///
+#[test]
+fn test_variant_fields_positional_mode() {
+    assert!(!VARIANT_TUPLE_STRUCT);
+    assert!(POSITIONAL_VARIANT_TUPLE_STRUCT);
+    assert!(POSITIONAL_VARIANT_STRUCT_NAMES);
+    assert!(POSITIONAL_VARIANT_IN_ENUM);
+    assert!(POSITIONAL_VARIANT_GENERIC_ENUM);
+    assert!(!POSITIONAL_VARIANT_MISMATCHED_TYPES);
+    assert!(!POSITIONAL_VARIANT_MISSING_FIELD);
+    assert!(!POSITIONAL_VARIANT_STRUCT_ITEM);
+    assert!(!POSITIONAL_VARIANT_LOOP);
+    assert!(!POSITIONAL_VARIANT_IMPL);
+    assert!(!POSITIONAL_VARIANT_STRUCT_LITERAL);
+    assert!(!POSITIONAL_VARIANT_OUTSIDE_ENUM);
+}
+
+invoke_tt_equal!(MACRO_CALL_SPACED {println!("x")} {println ! ("x")});
+invoke_tt_equal!(MACRO_CALL_SPACED_NESTED {f(vec!(1), format! ("{}", 2))} {f(vec ! (1), format!("{}", 2))});
+invoke_tt_equal!(MACRO_CALL_BRACKETS {println!("x")} {println!["x"]});
+invoke_tt_equal_in_mode!(IGNORED_DELIMITERS_MACRO_CALL [ignore_delimiters]
+    {println!("x")} {println ! ["x"]});
+invoke_tt_equal_in_mode!(IGNORED_DELIMITERS_MACRO_CALL_BRACES [ignore_delimiters]
+    {println!("x");} {println!{"x"};});
+invoke_tt_equal_in_mode!(IGNORED_DELIMITERS_DIFFERENT_CONTENTS [ignore_delimiters]
+    {println!("x")} {println!["y"]});
+invoke_tt_equal_in_mode!(IGNORED_DELIMITERS_NOT_A_MACRO [ignore_delimiters]
+    {println!("x")} {println("x")});
+
+///
+/// Tests that the spacing of the `!` of macro calls is ignored, while the delimiters of their
+/// arguments are only ignored in the ignore_delimiters mode.
+///
+#[test]
+fn test_macro_calls() {
+    assert!(MACRO_CALL_SPACED);
+    assert!(MACRO_CALL_SPACED_NESTED);
+    assert!(!MACRO_CALL_BRACKETS);
+    assert!(IGNORED_DELIMITERS_MACRO_CALL);
+    assert!(IGNORED_DELIMITERS_MACRO_CALL_BRACES);
+    assert!(!IGNORED_DELIMITERS_DIFFERENT_CONTENTS);
+    assert!(!IGNORED_DELIMITERS_NOT_A_MACRO);
+}
+
+invoke_tt_equal!(HEADER_DIFFERENT_BODIES {fn f(x: u8) -> u8 { x }} {fn f(x: u8) -> u8 { 0 }});
+invoke_tt_equal_in_mode!(HEADER_ONLY_DIFFERENT_BODIES [header_only]
+    {fn f(x: u8) -> u8 { x }} {fn f(x: u8) -> u8 { 0 }});
+invoke_tt_equal_in_mode!(HEADER_ONLY_WHERE_CLAUSE [header_only]
+    {fn f<T>(t: T) where T: Into<u8> { t.into() }} {fn f<T>(t: T) where T: Into<u8> { 1 }});
+invoke_tt_equal_in_mode!(HEADER_ONLY_DECLARATION [header_only]
+    {fn f(x: u8) -> u8 { x }} {fn f(x: u8) -> u8;});
+invoke_tt_equal_in_mode!(HEADER_ONLY_CONST [header_only]
+    {const X: u8 = 1;} {const X: u8 = 2;});
+invoke_tt_equal_in_mode!(HEADER_ONLY_GENERIC_DEFAULT [header_only]
+    {struct S<T = u8> { t: T }} {struct S<T = u16> { t: T }});
+invoke_tt_equal_in_mode!(HEADER_ONLY_DIFFERENT_SIGNATURES [header_only]
+    {fn f(x: u8) -> u8 { x }} {fn f(x: u16) -> u8 { x }});
+invoke_tt_equal_in_mode!(HEADER_ONLY_DIFFERENT_RETURN [header_only]
+    {fn f(x: u8) -> u8 { x }} {fn f(x: u8) -> u16 { x }});
+invoke_tt_equal_in_mode!(HEADER_ONLY_RETURN_ARRAY_LENGTH [header_only]
+    {fn f() -> [u8; 4] { [0; 4] }} {fn f() -> [u8; 5] { [0; 4] }});
+invoke_tt_equal_in_mode!(HEADER_ONLY_PARAMETER_ARRAY_LENGTH [header_only]
+    {fn f(x: [u8; 4]) {}} {fn f(x: [u8; 9]) {}});
+invoke_tt_equal_in_mode!(HEADER_ONLY_SAME_ARRAYS [header_only]
+    {fn f(x: [u8; 4]) -> [u8; 4] { x }} {fn f(x: [u8; 4]) -> [u8; 4] { [0; 4] }});
+
+///
+/// Tests that the header_only mode compares declarations up to their bodies.
+///
+#[test]
+fn test_header_only_mode() {
+    assert!(!HEADER_DIFFERENT_BODIES);
+    assert!(HEADER_ONLY_DIFFERENT_BODIES);
+    assert!(HEADER_ONLY_WHERE_CLAUSE);
+    assert!(HEADER_ONLY_DECLARATION);
+    assert!(HEADER_ONLY_CONST);
+    assert!(!HEADER_ONLY_GENERIC_DEFAULT);
+    assert!(!HEADER_ONLY_DIFFERENT_SIGNATURES);
+    assert!(!HEADER_ONLY_DIFFERENT_RETURN);
+    assert!(!HEADER_ONLY_RETURN_ARRAY_LENGTH);
+    assert!(!HEADER_ONLY_PARAMETER_ARRAY_LENGTH);
+    assert!(HEADER_ONLY_SAME_ARRAYS);
+}
+
+///
+/// Invokes `tt_equal` like `invoke_tt_equal_in_mode`, except the left-hand side is given as
+/// an expression, which `macro_rules!` passes on wrapped in an undelimited group.
+///
+macro_rules! invoke_tt_equal_in_mode_on_expr {
+    {
+        $id:ident [$($mode:ident)*] $lhs:expr, $rhs:tt
+    } => {
+        invoke_tt_equal_in_mode!($id [$($mode)*] $lhs $rhs);
+    }
+}
+
+invoke_tt_equal_in_mode_on_expr!(EXPR_IDENT [] x, x);
+invoke_tt_equal_in_mode_on_expr!(STRICT_EXPR_IDENT [strict] x, x);
+invoke_tt_equal_in_mode!(STRICT_IDENTS [strict] x x);
+invoke_tt_equal_in_mode!(STRICT_GROUPS[strict](x + 1)(x + 1));
+invoke_tt_equal_in_mode!(STRICT_DIFFERENT_IDENTS [strict] x y);
+
+///
+/// Tests that the strict mode requires token trees to be of the same kind.
+///
+#[test]
+fn test_strict_mode() {
+    assert!(EXPR_IDENT);
+    assert!(!STRICT_EXPR_IDENT);
+    assert!(STRICT_IDENTS);
+    assert!(STRICT_GROUPS);
+    assert!(!STRICT_DIFFERENT_IDENTS);
+}
+
+///
+/// We use this macro to invoke 'tt_equal' with `emit_applied` and produce a `&str` const of
+/// the modes it outputs as applied.
+///
+macro_rules! invoke_tt_equal_emit_applied {
+    {
+        $id:ident [ $($mode:tt)* ] $tt1:tt $tt2:tt
+    } => {
+        tt_call! {
+            macro = [{ tt_equal }]
+            input = [{ $tt1 $tt2 }]
+            mode = [{ $($mode)* }]
+            emit_applied = [{ true }]
+            ~~> applied_const! {
+                name = [{ $id }]
+            }
+        }
+    }
+}
+
+///
+/// Produces a `&str` const with the given name from the modes output by 'tt_equal'.
+///
+macro_rules! applied_const {
+    {
+        name = [{ $name:ident }]
+        is_equal = [{ $is_equal:tt }]
+        applied = [{ $($applied:ident)* }]
+    } => {
+        const $name: (bool, &str) = ($is_equal, stringify!($($applied)*));
+    }
+}
+
+invoke_tt_equal_emit_applied!(APPLIED_NONE [] 1_000 1_000);
+invoke_tt_equal_emit_applied!(APPLIED_SEMANTIC [semantic case_insensitive] 1_000 0x3E8);
+invoke_tt_equal_emit_applied!(APPLIED_BOTH [semantic case_insensitive] (Foo 1_000) (foo 0x3E8));
+invoke_tt_equal_emit_applied!(APPLIED_UNEQUAL [semantic] (Foo 1_000) (foo 0x3E8));
+invoke_tt_equal_emit_applied!(APPLIED_ORDER [ignore_delimiters semantic] (1_000) [0x3E8]);
+
+///
+/// Tests that `emit_applied` outputs the modes the result depends on.
+///
+#[test]
+fn test_emit_applied() {
+    assert_eq!(APPLIED_NONE, (true, ""));
+    assert_eq!(APPLIED_SEMANTIC, (true, "semantic"));
+    assert_eq!(APPLIED_BOTH, (true, "semantic case_insensitive"));
+    assert_eq!(APPLIED_UNEQUAL, (false, ""));
+    assert_eq!(APPLIED_ORDER, (true, "semantic ignore_delimiters"));
+}
+
+///
+/// Invokes 'tt_equal' with `echo` on the given input and modes, producing a
+/// `(bool, &str)` const of the result and the stringified canonical tokens.
+///
+macro_rules! invoke_tt_equal_echo {
+    {
+        $id:ident [ $($mode:tt)* ] $tt1:tt $tt2:tt
+    } => {
+        tt_call! {
+            macro = [{ tt_equal }]
+            input = [{ $tt1 $tt2 }]
+            mode = [{ $($mode)* }]
+            echo = [{ true }]
+            ~~> canonical_const! {
+                name = [{ $id }]
+            }
+        }
+    }
+}
+
+///
+/// Produces a `(bool, &str)` const with the given name from the canonical tokens output
+/// by 'tt_equal'.
+///
+macro_rules! canonical_const {
+    {
+        name = [{ $name:ident }]
+        is_equal = [{ $is_equal:tt }]
+        canonical = [{ $($canonical:tt)* }]
+    } => {
+        const $name: (bool, &str) = ($is_equal, stringify!($($canonical)*));
+    };
+}
+
+invoke_tt_equal_echo!(ECHO_EQUAL [] ( a , b ) (a,b));
+invoke_tt_equal_echo!(ECHO_SEMANTIC [semantic] 1_000 0x3E8);
+invoke_tt_equal_echo!(ECHO_UNEQUAL [] (a, b) (a, c));
+
+///
+/// Tests that `echo` outputs the compared token tree when the sides are equal.
+///
+#[test]
+fn test_echo() {
+    assert_eq!(ECHO_EQUAL, (true, stringify!((a, b))));
+    assert_eq!(ECHO_SEMANTIC, (true, "1_000"));
+    assert_eq!(ECHO_UNEQUAL, (false, ""));
+}
+
+invoke_tt_equal!(TRY_DESUGARED {foo()?} {match foo() { Ok(v) => v, Err(e) => return Err(e.into()) }});
+invoke_tt_equal_in_mode!(DESUGARED_TRY [desugar_try]
+    {foo()?} {match foo() { Ok(v) => v, Err(e) => return Err(e.into()) }});
+invoke_tt_equal_in_mode!(DESUGARED_TRY_STATEMENT [desugar_try]
+    {let x = a.b(1)?; x}
+    {let x = match a.b(1) { Ok(value) => value, Err(error) => return Err(error.into()), }; x});
+invoke_tt_equal_in_mode!(DESUGARED_TRY_NESTED [desugar_try]
+    {f(foo()?)} {f(match foo() { Ok(v) => v, Err(e) => return Err(e.into()) })});
+invoke_tt_equal_in_mode!(DESUGARED_TRY_BLOCK_SCRUTINEE [desugar_try]
+    {unsafe { f() }?} {match unsafe { f() } { Ok(v) => v, Err(e) => return Err(e.into()) }});
+invoke_tt_equal_in_mode!(DESUGARED_TRY_CONSECUTIVE [desugar_try]
+    {a()?+b()?}
+    {match a() { Ok(v) => v, Err(e) => return Err(e.into()) }
+        + match b() { Ok(v) => v, Err(e) => return Err(e.into()) }});
+invoke_tt_equal_in_mode!(DESUGARED_TRY_DIFFERENT_ERR [desugar_try]
+    {foo()?} {match foo() { Ok(v) => v, Err(e) => return Err(From::from(e)) }});
+invoke_tt_equal_in_mode!(DESUGARED_TRY_MISMATCHED_BINDING [desugar_try]
+    {foo()?} {match foo() { Ok(v) => w, Err(e) => return Err(e.into()) }});
+invoke_tt_equal_in_mode!(DESUGARED_TRY_DIFFERENT_EXPRESSION [desugar_try]
+    {foo()?} {match bar() { Ok(v) => v, Err(e) => return Err(e.into()) }});
+
+///
+/// Tests that the desugar_try mode compares the canonical desugaring of `?` as `?`.
+///
+#[test]
+fn test_desugar_try_mode() {
+    assert!(!TRY_DESUGARED);
+    assert!(DESUGARED_TRY);
+    assert!(DESUGARED_TRY_STATEMENT);
+    assert!(DESUGARED_TRY_NESTED);
+    assert!(DESUGARED_TRY_BLOCK_SCRUTINEE);
+    assert!(DESUGARED_TRY_CONSECUTIVE);
+    assert!(!DESUGARED_TRY_DIFFERENT_ERR);
+    assert!(!DESUGARED_TRY_MISMATCHED_BINDING);
+    assert!(!DESUGARED_TRY_DIFFERENT_EXPRESSION);
+}
+
+invoke_tt_equal_in_mode!(WIDTH_DEPENDENT [semantic] 255u8 255u16);
+invoke_tt_equal_in_mode!(WIDTH_INDEPENDENT [semantic width_independent] 255u8 255u16);
+invoke_tt_equal_in_mode!(WIDTH_INDEPENDENT_BASES [semantic width_independent] 0xFFu8 0b1111_1111u64);
+invoke_tt_equal_in_mode!(WIDTH_INDEPENDENT_SIZE [semantic width_independent] 255usize 0o377u32);
+invoke_tt_equal_in_mode!(WIDTH_INDEPENDENT_SIGNED [semantic width_independent] 255i16 255isize);
+invoke_tt_equal_in_mode!(WIDTH_INDEPENDENT_DIFFERENT_VALUES [semantic width_independent]
+    255u8 256u16);
+invoke_tt_equal_in_mode!(WIDTH_INDEPENDENT_DIFFERENT_SIGNEDNESS [semantic width_independent]
+    255u8 255i16);
+invoke_tt_equal_in_mode!(WIDTH_INDEPENDENT_UNSUFFIXED [semantic width_independent] 255u8 255);
+invoke_tt_equal_in_mode!(WIDTH_INDEPENDENT_NOT_SEMANTIC [width_independent] 255u8 255u16);
+
+///
+/// Tests that the width_independent mode makes the semantic mode ignore the widths of
+/// integer literals' suffixes.
+///
+#[test]
+fn test_width_independent_mode() {
+    assert!(!WIDTH_DEPENDENT);
+    assert!(WIDTH_INDEPENDENT);
+    assert!(WIDTH_INDEPENDENT_BASES);
+    assert!(WIDTH_INDEPENDENT_SIZE);
+    assert!(WIDTH_INDEPENDENT_SIGNED);
+    assert!(!WIDTH_INDEPENDENT_DIFFERENT_VALUES);
+    assert!(!WIDTH_INDEPENDENT_DIFFERENT_SIGNEDNESS);
+    assert!(!WIDTH_INDEPENDENT_UNSUFFIXED);
+    assert!(!WIDTH_INDEPENDENT_NOT_SEMANTIC);
+}
+
+invoke_tt_equal!(ATTR_PATH_ROOTED {#[::serde::Serialize] struct S;} {#[serde::Serialize] struct S;});
+invoke_tt_equal_in_mode!(UNROOTED_ATTR_PATH [unrooted_attr_paths]
+    {#[::serde::Serialize] struct S;} {#[serde::Serialize] struct S;});
+invoke_tt_equal_in_mode!(UNROOTED_INNER_ATTR_PATH [unrooted_attr_paths]
+    {#![::rustfmt::skip]} {#![rustfmt::skip]});
+invoke_tt_equal_in_mode!(UNROOTED_ATTR_PATH_NESTED [unrooted_attr_paths]
+    {mod m { #[::serde::Serialize] struct S; }} {mod m { #[serde::Serialize] struct S; }});
+invoke_tt_equal_in_mode!(UNROOTED_ATTR_PATH_DIFFERENT [unrooted_attr_paths]
+    {#[::serde::Serialize] struct S;} {#[serde::Deserialize] struct S;});
+invoke_tt_equal_in_mode!(UNROOTED_ATTR_PATH_ARGUMENTS [unrooted_attr_paths]
+    {#[derive(::serde::Serialize)] struct S;} {#[derive(serde::Serialize)] struct S;});
+invoke_tt_equal_in_mode!(UNROOTED_ATTR_PATH_OUTSIDE_ATTR [unrooted_attr_paths]
+    {use ::serde::Serialize;} {use serde::Serialize;});
+
+///
+/// Tests that the unrooted_attr_paths mode ignores the leading `::` of attribute paths.
+///
+#[test]
+fn test_unrooted_attr_paths_mode() {
+    assert!(!ATTR_PATH_ROOTED);
+    assert!(UNROOTED_ATTR_PATH);
+    assert!(UNROOTED_INNER_ATTR_PATH);
+    assert!(UNROOTED_ATTR_PATH_NESTED);
+    assert!(!UNROOTED_ATTR_PATH_DIFFERENT);
+    assert!(!UNROOTED_ATTR_PATH_ARGUMENTS);
+    assert!(!UNROOTED_ATTR_PATH_OUTSIDE_ATTR);
+}
+
+invoke_tt_equal!(AND_AND {& &} {&&});
+invoke_tt_equal_in_mode!(EXACT_AND_AND [exact] {& &} {&&});
+invoke_tt_equal_in_mode!(EXACT_SAME [exact] {a && (b, 1)} {a&&( b,1 )});
+invoke_tt_equal_in_mode_on_expr!(EXACT_EXPR_IDENT [exact] x, x);
+invoke_tt_equal_in_mode!(EXACT_IGNORES_MODES [exact semantic case_insensitive] {A 0x10} {a 16});
+
+///
+/// Tests that the exact mode compares token trees exactly, ignoring all other modes.
+///
+#[test]
+fn test_exact_mode() {
+    assert!(!AND_AND);
+    assert!(!EXACT_AND_AND);
+    assert!(EXACT_SAME);
+    assert!(!EXACT_EXPR_IDENT);
+    assert!(!EXACT_IGNORES_MODES);
+}
+
+invoke_tt_equal!(BOX_FN_FN {Box<dyn Fn()>} {Box<dyn Fn()>});
+invoke_tt_equal!(BOX_FN_FN_MUT {Box<dyn Fn()>} {Box<dyn FnMut()>});
+invoke_tt_equal!(BOX_FN_FN_ONCE {Box<dyn Fn()>} {Box<dyn FnOnce()>});
+invoke_tt_equal!(BOX_FN_MUT_FN_ONCE {Box<dyn FnMut()>} {Box<dyn FnOnce()>});
+invoke_tt_equal!(NESTED_BOX_FN_FN_MUT (f: Vec<Box<dyn Fn(u8)>>) (f: Vec<Box<dyn FnMut(u8)>>));
+
+///
+/// Tests that the `Fn`, `FnMut`, and `FnOnce` traits are never equal by default.
+///
+#[test]
+fn test_fn_traits() {
+    assert!(BOX_FN_FN);
+    assert!(!BOX_FN_FN_MUT);
+    assert!(!BOX_FN_FN_ONCE);
+    assert!(!BOX_FN_MUT_FN_ONCE);
+    assert!(!NESTED_BOX_FN_FN_MUT);
+}
+
+invoke_tt_equal!(GEN_BLOCKS {gen { yield 1; }} {gen {yield 1;}});
+invoke_tt_equal!(GEN_BLOCKS_DIFFERENT {gen { yield 1; }} {gen { yield 2; }});
+invoke_tt_equal!(GEN_ASYNC_BLOCKS {gen { yield 1; }} {async { yield 1; }});
+invoke_tt_equal_in_mode!(GEN_BLOCKS_KEYWORD_MODES
+    [ignore_const_fn normalize_bounds_position normalize_extern_crate desugar_try header_only]
+    {const gen fn f<T: Clone>() { gen { yield 1; } }}
+    {const gen fn f<T: Clone>() { gen { yield 2; } }});
+invoke_tt_equal_in_mode!(TRY_BLOCKS_KEYWORD_MODES [ignore_const_fn desugar_try]
+    {let x: Result<u8, E> = try { f()? };} {let x: Result<u8, E> = try { f()? };});
+
+///
+/// Tests that unknown keywords, like those of future syntax, are compared like any other
+/// identifier, also in modes that look for keywords.
+///
+#[test]
+fn test_future_keywords() {
+    assert!(GEN_BLOCKS);
+    assert!(!GEN_BLOCKS_DIFFERENT);
+    assert!(!GEN_ASYNC_BLOCKS);
+    assert!(GEN_BLOCKS_KEYWORD_MODES);
+    assert!(TRY_BLOCKS_KEYWORD_MODES);
+}
+
+invoke_tt_equal_in_mode!(LINE_ENDINGS [semantic] "a\r\nb\r\n" "a\nb\n");
+invoke_tt_equal_in_mode!(NORMALIZED_LINE_ENDINGS [semantic normalize_line_endings]
+    "a\r\nb\r\n" "a\nb\n");
+invoke_tt_equal_in_mode!(NORMALIZED_LINE_ENDINGS_CR [semantic normalize_line_endings]
+    "a\rb" r"a
+b");
+invoke_tt_equal_in_mode!(NORMALIZED_LINE_ENDINGS_DIFFERENT [semantic normalize_line_endings]
+    "a\r\nb" "a\nc");
+invoke_tt_equal_in_mode!(NORMALIZED_LINE_ENDINGS_DOUBLE [semantic normalize_line_endings]
+    "a\r\nb" "a\n\nb");
+invoke_tt_equal_in_mode!(NORMALIZED_LINE_ENDINGS_NOT_SEMANTIC [normalize_line_endings]
+    "a\r\nb" "a\nb");
+
+///
+/// Tests that the normalize_line_endings mode makes the semantic mode ignore the line
+/// endings of strings.
+///
+#[test]
+fn test_normalize_line_endings_mode() {
+    assert!(!LINE_ENDINGS);
+    assert!(NORMALIZED_LINE_ENDINGS);
+    assert!(NORMALIZED_LINE_ENDINGS_CR);
+    assert!(!NORMALIZED_LINE_ENDINGS_DIFFERENT);
+    assert!(!NORMALIZED_LINE_ENDINGS_DOUBLE);
+    assert!(!NORMALIZED_LINE_ENDINGS_NOT_SEMANTIC);
+}
+
+invoke_tt_equal_on_input!(LIFETIMES ['a 'a]);
+invoke_tt_equal_on_input!(DIFFERENT_LIFETIMES ['a 'b]);
+invoke_tt_equal_on_input!(STATIC_LIFETIMES ['static 'static]);
+invoke_tt_equal_on_input!(LIFETIME_IDENT ['a a]);
+
+///
+/// Tests that a lifetime is a single token tree that can be compared on its own.
+///
+#[test]
+fn test_lifetimes() {
+    assert!(LIFETIMES);
+    assert!(!DIFFERENT_LIFETIMES);
+    assert!(STATIC_LIFETIMES);
+    assert!(!LIFETIME_IDENT);
+    assert!(FORWARDED_LIFETIMES);
+    assert!(!FORWARDED_DIFFERENT_LIFETIMES);
+}
+
+///
+/// We use this macro to invoke 'tt_equal' on two lifetimes matched by the `lifetime`
+/// fragment specifier, and produce a bool const of whether they were equal.
+///
+macro_rules! invoke_tt_equal_on_lifetimes {
+    {
+        $id1:ident $lt1:lifetime $lt2:lifetime
+    } => {
+        invoke_tt_equal_on_input!($id1 [$lt1 $lt2]);
+    }
+}
+
+invoke_tt_equal_on_lifetimes!(FORWARDED_LIFETIMES 'outer 'outer);
+invoke_tt_equal_on_lifetimes!(FORWARDED_DIFFERENT_LIFETIMES 'outer 'inner);
+
+invoke_tt_equal!(LOOP_LABELS {'a: loop {}} {'b: loop {}});
+invoke_tt_equal!(LOOP_LABELS_SPACED {'a: loop { break 'a; }} {'a : loop { break 'a ; }});
+invoke_tt_equal_in_mode!(IGNORED_LOOP_LABELS [ignore_loop_labels] {'a: loop {}} {'b: loop {}});
+invoke_tt_equal_in_mode!(IGNORED_LOOP_LABELS_BREAK [ignore_loop_labels]
+    {'a: while x { continue 'a; }} {'b: while x { continue 'b; }});
+invoke_tt_equal_in_mode!(IGNORED_LOOP_LABELS_BLOCK [ignore_loop_labels]
+    {'a: { break 'a 1; }} {'b: { break 'b 1; }});
+invoke_tt_equal_in_mode!(IGNORED_LOOP_LABELS_BODY [ignore_loop_labels]
+    {'a: loop { break 'a; }} {'b: loop { continue 'b; }});
+invoke_tt_equal_in_mode!(IGNORED_LOOP_LABELS_LIFETIMES [ignore_loop_labels]
+    {fn f<'a: 'b, 'b>(x: &'a str) {}} {fn f<'c: 'b, 'b>(x: &'c str) {}});
+
+///
+/// Tests that labels compare like lifetimes by default, and regardless of their names in the
+/// ignore_loop_labels mode.
+///
+#[test]
+fn test_ignore_loop_labels_mode() {
+    assert!(!LOOP_LABELS);
+    assert!(LOOP_LABELS_SPACED);
+    assert!(IGNORED_LOOP_LABELS);
+    assert!(IGNORED_LOOP_LABELS_BREAK);
+    assert!(IGNORED_LOOP_LABELS_BLOCK);
+    assert!(!IGNORED_LOOP_LABELS_BODY);
+    assert!(!IGNORED_LOOP_LABELS_LIFETIMES);
+}
+
+invoke_tt_equal!(IDENT_NUMERIC_SUFFIXES tmp1 tmp2);
+invoke_tt_equal_in_mode!(STRIPPED_IDENT_NUMERIC_SUFFIXES [strip_ident_numeric_suffix] tmp1 tmp2);
+invoke_tt_equal_in_mode!(STRIPPED_IDENT_NUMERIC_SUFFIX_LONG [strip_ident_numeric_suffix] tmp1 tmp42);
+invoke_tt_equal_in_mode!(STRIPPED_IDENT_NUMERIC_SUFFIX_NONE [strip_ident_numeric_suffix] tmp1 tmp);
+invoke_tt_equal_in_mode!(STRIPPED_IDENT_NUMERIC_SUFFIX_NESTED [strip_ident_numeric_suffix]
+    {let tmp1 = tmp2 + 1;} {let tmp3 = tmp4 + 1;});
+invoke_tt_equal_in_mode!(STRIPPED_IDENT_NUMERIC_SUFFIX_DIFFERENT [strip_ident_numeric_suffix] foo1 bar1);
+invoke_tt_equal_in_mode!(STRIPPED_IDENT_NUMERIC_SUFFIX_INNER [strip_ident_numeric_suffix] a1b a2b);
+invoke_tt_equal_in_mode!(STRIPPED_IDENT_NUMERIC_SUFFIX_LITERAL [strip_ident_numeric_suffix] 1 2);
+invoke_tt_equal_in_mode!(STRIPPED_IDENT_NUMERIC_SUFFIX_CASE [strip_ident_numeric_suffix case_insensitive]
+    Tmp1 tmp2);
+
+///
+/// Tests that the strip_ident_numeric_suffix mode compares identifiers without their trailing
+/// digits.
+///
+#[test]
+fn test_strip_ident_numeric_suffix_mode() {
+    assert!(!IDENT_NUMERIC_SUFFIXES);
+    assert!(STRIPPED_IDENT_NUMERIC_SUFFIXES);
+    assert!(STRIPPED_IDENT_NUMERIC_SUFFIX_LONG);
+    assert!(STRIPPED_IDENT_NUMERIC_SUFFIX_NONE);
+    assert!(STRIPPED_IDENT_NUMERIC_SUFFIX_NESTED);
+    assert!(!STRIPPED_IDENT_NUMERIC_SUFFIX_DIFFERENT);
+    assert!(!STRIPPED_IDENT_NUMERIC_SUFFIX_INNER);
+    assert!(!STRIPPED_IDENT_NUMERIC_SUFFIX_LITERAL);
+    assert!(STRIPPED_IDENT_NUMERIC_SUFFIX_CASE);
+}
+
+invoke_tt_equal!(RAW_KEYWORD r#type type);
+invoke_tt_equal!(RAW_IDENT r#foo foo);
+invoke_tt_equal_in_mode!(RAW_INSENSITIVE_KEYWORD [raw_insensitive] r#type type);
+invoke_tt_equal_in_mode!(RAW_INSENSITIVE_IDENT [raw_insensitive] r#foo foo);
+invoke_tt_equal_in_mode!(RAW_INSENSITIVE_BOTH [raw_insensitive] r#match r#match);
+invoke_tt_equal_in_mode!(RAW_INSENSITIVE_NESTED [raw_insensitive] {let r#type = 1;} {let type = 1;});
+invoke_tt_equal_in_mode!(RAW_INSENSITIVE_DIFFERENT [raw_insensitive] r#type r#struct);
+invoke_tt_equal_in_mode!(RAW_INSENSITIVE_CASE [raw_insensitive case_insensitive] r#Foo foo);
+
+///
+/// Tests that the raw_insensitive mode compares identifiers regardless of an `r#` prefix,
+/// while they are distinct by default.
+///
+#[test]
+fn test_raw_insensitive_mode() {
+    assert!(!RAW_KEYWORD);
+    assert!(!RAW_IDENT);
+    assert!(RAW_INSENSITIVE_KEYWORD);
+    assert!(RAW_INSENSITIVE_IDENT);
+    assert!(RAW_INSENSITIVE_BOTH);
+    assert!(RAW_INSENSITIVE_NESTED);
+    assert!(!RAW_INSENSITIVE_DIFFERENT);
+    assert!(RAW_INSENSITIVE_CASE);
+}
+
+invoke_tt_equal!(USE_RENAMED {pub use foo::Bar as Baz;} {pub use foo::Bar;});
+invoke_tt_equal_in_mode!(IGNORED_USE_RENAME [ignore_use_rename]
+    {pub use foo::Bar as Baz;} {pub use foo::Bar;});
+invoke_tt_equal_in_mode!(IGNORED_USE_RENAME_BOTH [ignore_use_rename]
+    {use foo::Bar as Baz;} {use foo::Bar as Qux;});
+invoke_tt_equal_in_mode!(IGNORED_USE_RENAME_TREE [ignore_use_rename]
+    {use foo::{a as b, c::{d as _, e}};} {use foo::{a, c::{d, e}};});
+invoke_tt_equal_in_mode!(IGNORED_USE_RENAME_PATHS [ignore_use_rename]
+    {pub use foo::Bar as Baz;} {pub use bar::Bar as Baz;});
+invoke_tt_equal_in_mode!(IGNORED_USE_RENAME_QUALIFIED [ignore_use_rename]
+    {use foo::Bar; <T as Trait>::f();} {use foo::Bar; <T as Other>::f();});
+invoke_tt_equal_in_mode!(IGNORED_USE_RENAME_CAST [ignore_use_rename]
+    {use foo::Bar; let x = y as u8;} {use foo::Bar; let x = y as u16;});
+
+///
+/// Tests that the ignore_use_rename mode compares `use` items ignoring their renames, while
+/// other uses of `as` are still compared.
+///
+#[test]
+fn test_ignore_use_rename_mode() {
+    assert!(!USE_RENAMED);
+    assert!(IGNORED_USE_RENAME);
+    assert!(IGNORED_USE_RENAME_BOTH);
+    assert!(IGNORED_USE_RENAME_TREE);
+    assert!(!IGNORED_USE_RENAME_PATHS);
+    assert!(!IGNORED_USE_RENAME_QUALIFIED);
+    assert!(!IGNORED_USE_RENAME_CAST);
+}
+
+invoke_tt_equal_in_mode!(IGNORED_SUFFIX_U8 [ignore_suffixes] 1u8 1);
+invoke_tt_equal_in_mode!(IGNORED_SUFFIX_I32 [ignore_suffixes] 1u8 1i32);
+invoke_tt_equal_in_mode!(IGNORED_SUFFIX_UNDERSCORE [ignore_suffixes] 1_u8 1);
+invoke_tt_equal_in_mode!(IGNORED_SUFFIX_FLOAT [ignore_suffixes] 1.0f32 1.0);
+invoke_tt_equal_in_mode!(IGNORED_SUFFIX_INT_FLOAT [ignore_suffixes] 1 1.0);
+invoke_tt_equal_in_mode!(IGNORED_SUFFIX_DIFFERENT [ignore_suffixes] 1u8 2u8);
+invoke_tt_equal_in_mode!(IGNORED_SUFFIX_SPELLING [ignore_suffixes] 0x10u8 16);
+invoke_tt_equal_in_mode!(IGNORED_SUFFIX_SEMANTIC [ignore_suffixes semantic] 0x10u8 16);
+invoke_tt_equal_in_mode!(IGNORED_SUFFIX_EXACT [ignore_suffixes exact] 1u8 1);
+invoke_tt_equal!(SUFFIX_U8 1u8 1);
+
+///
+/// Tests that the ignore_suffixes mode ignores the type suffixes of numbers.
+///
+#[test]
+fn test_ignore_suffixes_mode() {
+    assert!(IGNORED_SUFFIX_U8);
+    assert!(IGNORED_SUFFIX_I32);
+    assert!(IGNORED_SUFFIX_UNDERSCORE);
+    assert!(IGNORED_SUFFIX_FLOAT);
+    assert!(!IGNORED_SUFFIX_INT_FLOAT);
+    assert!(!IGNORED_SUFFIX_DIFFERENT);
+    assert!(!IGNORED_SUFFIX_SPELLING);
+    assert!(IGNORED_SUFFIX_SEMANTIC);
+    assert!(!IGNORED_SUFFIX_EXACT);
+    assert!(!SUFFIX_U8);
+}
+
+///
+/// We use this macro to invoke 'tt_equal' with the `semantic` mode as a preset in `defaults`,
+/// which the given modes are added to.
+///
+macro_rules! invoke_tt_equal_with_defaults {
+    {
+        $id1:ident [ $($mode:tt)* ] $tt1:tt $tt2:tt
+    } => {
+        tt_call! {
+            macro = [{ tt_equal }]
+            defaults = [{ semantic }]
+            input = [{ $tt1 $tt2 }]
+            mode = [{ $($mode)* }]
+            ~~> bool_const! {
+                name = [{ $id1 }]
+            }
+        }
+    }
+}
+
+invoke_tt_equal_with_defaults!(DEFAULTS_ONLY [] 0x10 16);
+invoke_tt_equal_with_defaults!(DEFAULTS_ONLY_CASE [] (A 0x10) (a 16));
+invoke_tt_equal_with_defaults!(DEFAULTS_WITH_MODE [case_insensitive] (A 0x10) (a 16));
+invoke_tt_equal_with_defaults!(DEFAULTS_REPEATED_IN_MODE [semantic] 0x10 16);
+invoke_tt_equal_with_defaults!(DEFAULTS_DIFFERENT [case_insensitive] (A 0x10) (a 17));
+
+///
+/// Tests that the modes given in `defaults` are combined with the modes given in `mode`.
+///
+#[test]
+fn test_defaults() {
+    assert!(DEFAULTS_ONLY);
+    assert!(!DEFAULTS_ONLY_CASE);
+    assert!(DEFAULTS_WITH_MODE);
+    assert!(DEFAULTS_REPEATED_IN_MODE);
+    assert!(!DEFAULTS_DIFFERENT);
+}
+
+///
+/// We use this macro to invoke 'tt_equal' with the given `expected_type` and produce a bool
+/// const of whether the two sides of the given input were equal, taking signed numbers to be
+/// single sides.
+///
+macro_rules! invoke_tt_equal_with_type {
+    {
+        $id1:ident $ty:ident [ $($input:tt)* ]
+    } => {
+        tt_call! {
+            macro = [{ tt_equal }]
+            input = [{ $($input)* }]
+            mode = [{ signed_numbers }]
+            expected_type = [{ $ty }]
+            ~~> bool_const! {
+                name = [{ $id1 }]
+            }
+        }
+    }
+}
+
+invoke_tt_equal_with_type!(EXPECTED_TYPE_HEX u8 [255 0xFF]);
+invoke_tt_equal_with_type!(EXPECTED_TYPE_MAX u128 [340282366920938463463374607431768211455 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF]);
+invoke_tt_equal_with_type!(EXPECTED_TYPE_SUFFIX u16 [0x100u16 256]);
+invoke_tt_equal_with_type!(EXPECTED_TYPE_NEGATIVE i8 [-128 -0x80]);
+invoke_tt_equal_with_type!(EXPECTED_TYPE_ZERO i8 [-0 0]);
+invoke_tt_equal_with_type!(EXPECTED_TYPE_DIFFERENT u8 [255 0xFE]);
+invoke_tt_equal_with_type!(EXPECTED_TYPE_SIGN i8 [-1 1]);
+invoke_tt_equal_with_type!(EXPECTED_TYPE_NON_LITERAL u8 [a a]);
+invoke_tt_equal_with_type!(EXPECTED_TYPE_GROUP u8 [(255) (0xFF)]);
+
+///
+/// Tests that integer literals are compared by value when `expected_type` is given.
+///
+#[test]
+fn test_expected_type() {
+    assert!(EXPECTED_TYPE_HEX);
+    assert!(EXPECTED_TYPE_MAX);
+    assert!(EXPECTED_TYPE_SUFFIX);
+    assert!(EXPECTED_TYPE_NEGATIVE);
+    assert!(EXPECTED_TYPE_ZERO);
+    assert!(!EXPECTED_TYPE_DIFFERENT);
+    assert!(!EXPECTED_TYPE_SIGN);
+    assert!(EXPECTED_TYPE_NON_LITERAL);
+    assert!(!EXPECTED_TYPE_GROUP);
+}
+
+///
+/// Invokes 'tt_equal' with the given `max_depth` and modes, producing a bool const of whether
+/// the given input was equal.
+///
+macro_rules! invoke_tt_equal_with_max_depth {
+    {
+        $id1:ident $depth:tt [ $($mode:ident)* ] [ $($input:tt)* ]
+    } => {
+        tt_call! {
+            macro = [{ tt_equal }]
+            input = [{ $($input)* }]
+            mode = [{ $($mode)* }]
+            max_depth = [{ $depth }]
+            ~~> bool_const! {
+                name = [{ $id1 }]
+            }
+        }
+    }
+}
+
+invoke_tt_equal_with_max_depth!(MAX_DEPTH_WITHIN 1 [] [(a [b]) (a [ b ])]);
+invoke_tt_equal_with_max_depth!(MAX_DEPTH_BEYOND 1 [] [(a [b]) (a [b,])]);
+invoke_tt_equal_with_max_depth!(MAX_DEPTH_AT_BOUNDARY 2 [] [(a [b c]) (a [b, c])]);
+invoke_tt_equal_with_max_depth!(MAX_DEPTH_SEMANTIC_WITHIN 2 [semantic] [(1 [0x1]) (0x1 [1])]);
+invoke_tt_equal_with_max_depth!(MAX_DEPTH_SEMANTIC_BEYOND 1 [semantic] [(1 [0x1]) (0x1 [1])]);
+invoke_tt_equal_with_max_depth!(MAX_DEPTH_SEMANTIC_OUTSIDE 1 [semantic] [(1 [1]) (0x1 [1])]);
+invoke_tt_equal_with_max_depth!(MAX_DEPTH_ZERO 0 [] [(a b) ( a b )]);
+invoke_tt_equal_with_max_depth!(MAX_DEPTH_ZERO_SEMANTIC 0 [semantic] [(1) (0x1)]);
+invoke_tt_equal_with_max_depth!(MAX_DEPTH_ZERO_TOKENS 0 [semantic] [1 0x1]);
+
+///
+/// Tests that groups nested deeper than `max_depth` are compared by their string
+/// representations.
+///
+#[test]
+fn test_max_depth() {
+    assert!(MAX_DEPTH_WITHIN);
+    assert!(!MAX_DEPTH_BEYOND);
+    assert!(!MAX_DEPTH_AT_BOUNDARY);
+    assert!(MAX_DEPTH_SEMANTIC_WITHIN);
+    assert!(!MAX_DEPTH_SEMANTIC_BEYOND);
+    assert!(MAX_DEPTH_SEMANTIC_OUTSIDE);
+    assert!(MAX_DEPTH_ZERO);
+    assert!(!MAX_DEPTH_ZERO_SEMANTIC);
+    assert!(MAX_DEPTH_ZERO_TOKENS);
+}
+
+invoke_tt_equal!(ASSOC_BINDINGS_REORDERED
+    {Iterator<Item = u8, IntoIter = I>} {Iterator<IntoIter = I, Item = u8>});
+invoke_tt_equal_in_mode!(UNORDERED_ASSOC_BINDINGS [assoc_bindings_unordered]
+    {Iterator<Item = u8, IntoIter = I>} {Iterator<IntoIter = I, Item = u8>});
+invoke_tt_equal_in_mode!(UNORDERED_ASSOC_BINDINGS_POSITIONAL [assoc_bindings_unordered]
+    {fn f<T: Foo<A, Vec<u8>, X = u8, Y = u16>>() {}} {fn f<T: Foo<A, Vec<u8>, Y = u16, X = u8>>() {}});
+invoke_tt_equal_in_mode!(UNORDERED_ASSOC_BINDINGS_NESTED [assoc_bindings_unordered]
+    {Box<dyn Foo<X = Bar<A = u8, B = u16>, Y = u8>>} {Box<dyn Foo<Y = u8, X = Bar<B = u16, A = u8>>>});
+invoke_tt_equal_in_mode!(UNORDERED_ASSOC_BINDINGS_DIFFERENT [assoc_bindings_unordered]
+    {Iterator<Item = u8, IntoIter = I>} {Iterator<IntoIter = I, Item = u16>});
+invoke_tt_equal_in_mode!(UNORDERED_ASSOC_BINDINGS_POSITIONAL_ORDER [assoc_bindings_unordered]
+    {Foo<A, B, Item = u8>} {Foo<B, A, Item = u8>});
+invoke_tt_equal_in_mode!(UNORDERED_ASSOC_BINDINGS_COMPARISON [assoc_bindings_unordered]
+    {a < b && c > d} {a < b && c > d});
+
+///
+/// Tests that the assoc_bindings_unordered mode compares associated type bindings regardless
+/// of their order.
+///
+#[test]
+fn test_assoc_bindings_unordered_mode() {
+    assert!(!ASSOC_BINDINGS_REORDERED);
+    assert!(UNORDERED_ASSOC_BINDINGS);
+    assert!(UNORDERED_ASSOC_BINDINGS_POSITIONAL);
+    assert!(UNORDERED_ASSOC_BINDINGS_NESTED);
+    assert!(!UNORDERED_ASSOC_BINDINGS_DIFFERENT);
+    assert!(!UNORDERED_ASSOC_BINDINGS_POSITIONAL_ORDER);
+    assert!(UNORDERED_ASSOC_BINDINGS_COMPARISON);
+}
+
+invoke_tt_equal!(IMPL_BODIES_DIFFERENT {impl Foo for Bar { fn a() {} }} {impl Foo for Bar {}});
+invoke_tt_equal_in_mode!(IMPL_SUBJECT [compare_impl_subject_only]
+    {impl Foo for Bar { fn a() {} }} {impl Foo for Bar { fn b() -> u8 { 1 } }});
+invoke_tt_equal_in_mode!(IMPL_SUBJECT_GENERICS [compare_impl_subject_only]
+    {impl<T: Clone> Foo<T> for Bar<T> { fn a() {} }}
+    {impl<T> Foo<T> for Bar<T> where T: Copy {}});
+invoke_tt_equal_in_mode!(IMPL_SUBJECT_INHERENT [compare_impl_subject_only]
+    {#[inline] impl Bar { fn a() {} }} {#[inline] impl Bar {}});
+invoke_tt_equal_in_mode!(IMPL_SUBJECT_SEVERAL [compare_impl_subject_only]
+    {impl Foo for Bar { fn a() {} } impl Foo for Baz {}}
+    {impl Foo for Bar {} impl Foo for Baz { fn a() {} }});
+invoke_tt_equal_in_mode!(IMPL_SUBJECT_DIFFERENT_TYPE [compare_impl_subject_only]
+    {impl Foo for Bar {}} {impl Foo for Baz {}});
+invoke_tt_equal_in_mode!(IMPL_SUBJECT_DIFFERENT_TRAIT [compare_impl_subject_only]
+    {impl Foo for Bar {}} {impl Qux for Bar {}});
+invoke_tt_equal_in_mode!(IMPL_SUBJECT_RETURN_TYPE [compare_impl_subject_only]
+    {fn f() -> impl Foo { 1 }} {fn f() -> impl Foo { 2 }});
+
+///
+/// Tests that the compare_impl_subject_only mode compares impl blocks by their subject only.
+///
+#[test]
+fn test_compare_impl_subject_only_mode() {
+    assert!(!IMPL_BODIES_DIFFERENT);
+    assert!(IMPL_SUBJECT);
+    assert!(IMPL_SUBJECT_GENERICS);
+    assert!(IMPL_SUBJECT_INHERENT);
+    assert!(IMPL_SUBJECT_SEVERAL);
+    assert!(!IMPL_SUBJECT_DIFFERENT_TYPE);
+    assert!(!IMPL_SUBJECT_DIFFERENT_TRAIT);
+    assert!(!IMPL_SUBJECT_RETURN_TYPE);
+}
+
+invoke_tt_equal!(IMPL_GENERICS_RENAMED {impl<T> Foo for Bar<T> {}} {impl<U> Foo for Bar<U> {}});
+invoke_tt_equal_in_mode!(IGNORED_IMPL_GENERICS_NAMES [ignore_impl_generics_names]
+    {impl<T> Foo for Bar<T> { fn a(t: T) {} }} {impl<U> Foo for Bar<U> { fn a(t: U) {} }});
+invoke_tt_equal_in_mode!(IGNORED_IMPL_GENERICS_NAMES_KINDS [ignore_impl_generics_names]
+    {impl<'a, T: Iterator<Item = &'a u8>, const N: usize> Foo<N> for Bar<'a, T> {}}
+    {impl<'b, I: Iterator<Item = &'b u8>, const M: usize> Foo<M> for Bar<'b, I> {}});
+invoke_tt_equal_in_mode!(IGNORED_IMPL_GENERICS_NAMES_SWAPPED [ignore_impl_generics_names]
+    {impl<T, U> Foo for Bar<T, U> {}} {impl<T, U> Foo for Bar<U, T> {}});
+invoke_tt_equal_in_mode!(IGNORED_IMPL_GENERICS_NAMES_SUBJECT
+    [ignore_impl_generics_names compare_impl_subject_only]
+    {impl<T: Clone> Foo for Bar<T> { fn a() {} }} {impl<U> Foo for Bar<U> {}});
+invoke_tt_equal_in_mode!(IGNORED_IMPL_GENERICS_NAMES_LIFETIME_IDENT [ignore_impl_generics_names]
+    {impl<'a> S<'a> { fn a() {} }} {impl<'b> S<'b> { fn b() {} }});
+invoke_tt_equal_in_mode!(IGNORED_IMPL_GENERICS_NAMES_LIFETIME_PARAM [ignore_impl_generics_names]
+    {impl<'a> S<'a> { fn f(a: &'a u8) {} }} {impl<'b> S<'b> { fn f(a: &'b u8) {} }});
+invoke_tt_equal_in_mode!(IGNORED_IMPL_GENERICS_NAMES_TYPE_LIFETIME [ignore_impl_generics_names]
+    {impl<'a, T> S<'a, T> { fn f(x: &'T u8) {} }} {impl<'b, U> S<'b, U> { fn f(x: &'U u8) {} }});
+invoke_tt_equal_in_mode!(IGNORED_IMPL_GENERICS_NAMES_SHADOWED [ignore_impl_generics_names]
+    {impl<T> Foo for Bar<T> { fn a<T>(t: T) {} }} {impl<U> Foo for Bar<U> { fn a<T>(t: T) {} }});
+invoke_tt_equal_in_mode!(IGNORED_IMPL_GENERICS_NAMES_SHADOWED_OUTER [ignore_impl_generics_names]
+    {impl<T> Foo for Bar<T> { fn a<U>(t: T) {} }} {impl<U> Foo for Bar<U> { fn a<U>(t: U) {} }});
+invoke_tt_equal_in_mode!(IGNORED_IMPL_GENERICS_NAMES_PATH [ignore_impl_generics_names]
+    {impl<T> Foo for Bar<T> { fn a() -> Self::T {} }} {impl<U> Foo for Bar<U> { fn a() -> Self::T {} }});
+invoke_tt_equal_in_mode!(IGNORED_IMPL_GENERICS_NAMES_PATH_DIFFERENT [ignore_impl_generics_names]
+    {impl<T> Foo for Bar<T> { fn a() -> Self::T {} }} {impl<U> Foo for Bar<U> { fn a() -> Self::U {} }});
+
+///
+/// Tests that the ignore_impl_generics_names mode compares the generic parameters of impl
+/// blocks by their position, except where they are shadowed or follow a `::`.
+///
+#[test]
+fn test_ignore_impl_generics_names_mode() {
+    assert!(!IMPL_GENERICS_RENAMED);
+    assert!(IGNORED_IMPL_GENERICS_NAMES);
+    assert!(IGNORED_IMPL_GENERICS_NAMES_KINDS);
+    assert!(!IGNORED_IMPL_GENERICS_NAMES_SWAPPED);
+    assert!(IGNORED_IMPL_GENERICS_NAMES_SUBJECT);
+    assert!(!IGNORED_IMPL_GENERICS_NAMES_LIFETIME_IDENT);
+    assert!(IGNORED_IMPL_GENERICS_NAMES_LIFETIME_PARAM);
+    assert!(!IGNORED_IMPL_GENERICS_NAMES_TYPE_LIFETIME);
+    assert!(IGNORED_IMPL_GENERICS_NAMES_SHADOWED);
+    assert!(!IGNORED_IMPL_GENERICS_NAMES_SHADOWED_OUTER);
+    assert!(IGNORED_IMPL_GENERICS_NAMES_PATH);
+    assert!(!IGNORED_IMPL_GENERICS_NAMES_PATH_DIFFERENT);
+}
+
+invoke_tt_equal_in_mode!(IGNORED_DELIMITERS_PARENS_BRACKETS[ignore_delimiters](a)[a]);
+invoke_tt_equal_in_mode!(IGNORED_DELIMITERS_PARENS_BRACES [ignore_delimiters] (a b) {a b});
+invoke_tt_equal_in_mode!(IGNORED_DELIMITERS_NESTED [ignore_delimiters] ([a], {b}) {(a), [b]});
+invoke_tt_equal_in_mode!(IGNORED_DELIMITERS_CONTENTS[ignore_delimiters](a)[b]);
+invoke_tt_equal_in_mode!(IGNORED_DELIMITERS_GROUP_TOKEN [ignore_delimiters] (a) a);
+
+///
+/// Tests that the ignore_delimiters mode compares groups by their contents only.
+///
+#[test]
+fn test_ignore_delimiters_mode() {
+    assert!(!GROUP_DIFFERENT_DELIMITERS);
+    assert!(IGNORED_DELIMITERS_PARENS_BRACKETS);
+    assert!(IGNORED_DELIMITERS_PARENS_BRACES);
+    assert!(IGNORED_DELIMITERS_NESTED);
+    assert!(!IGNORED_DELIMITERS_CONTENTS);
+    assert!(!IGNORED_DELIMITERS_GROUP_TOKEN);
+}
+
+invoke_tt_equal!(TYPE_ASCRIPTION_MISSING {const X: i32 = 5;} {const X = 5;});
+invoke_tt_equal_in_mode!(IGNORED_TYPE_ASCRIPTION [ignore_type_ascription]
+    {const X: i32 = 5;} {const X = 5;});
+invoke_tt_equal_in_mode!(IGNORED_TYPE_ASCRIPTION_GENERIC [ignore_type_ascription]
+    {let x: HashMap<u8, u16> = y;} {let x = y;});
+invoke_tt_equal_in_mode!(IGNORED_TYPE_ASCRIPTION_PARAMS [ignore_type_ascription]
+    {fn f(a: u8, b: Vec<u8>) {}} {fn f(a, b) {}});
+invoke_tt_equal_in_mode!(IGNORED_TYPE_ASCRIPTION_PATH [ignore_type_ascription]
+    {const X: i32 = a::b;} {const X = a::c;});
+invoke_tt_equal_in_mode!(IGNORED_TYPE_ASCRIPTION_NAMES [ignore_type_ascription]
+    {const X: i32 = 5;} {const Y = 5;});
+invoke_tt_equal_in_mode!(IGNORED_TYPE_ASCRIPTION_VALUES [ignore_type_ascription]
+    {static X: i32 = 5;} {static X: i32 = 6;});
+
+///
+/// Tests that the ignore_type_ascription mode ignores the types ascribed after `:`.
+///
+#[test]
+fn test_ignore_type_ascription_mode() {
+    assert!(!TYPE_ASCRIPTION_MISSING);
+    assert!(IGNORED_TYPE_ASCRIPTION);
+    assert!(IGNORED_TYPE_ASCRIPTION_GENERIC);
+    assert!(IGNORED_TYPE_ASCRIPTION_PARAMS);
+    assert!(!IGNORED_TYPE_ASCRIPTION_PATH);
+    assert!(!IGNORED_TYPE_ASCRIPTION_NAMES);
+    assert!(!IGNORED_TYPE_ASCRIPTION_VALUES);
+}
+
+invoke_tt_equal_in_mode!(STRING_CASE [semantic] "Hello" "hello");
+invoke_tt_equal_in_mode!(STR_IGNORED_CASE [semantic str_ignore_case] "Hello" "hello");
+invoke_tt_equal_in_mode!(STR_IGNORED_CASE_ESCAPED [semantic str_ignore_case] "Hello" "\x48ELLO");
+invoke_tt_equal_in_mode!(STR_IGNORED_CASE_RAW [semantic str_ignore_case] r"Hello" "HELLO");
+invoke_tt_equal_in_mode!(STR_IGNORED_CASE_CONTENT [semantic str_ignore_case] "Hello" "Help");
+invoke_tt_equal_in_mode!(STR_IGNORED_CASE_NOT_SEMANTIC [str_ignore_case] "Hello" "hello");
+invoke_tt_equal_in_mode!(STR_IGNORED_CASE_IDENTS [semantic str_ignore_case] Hello hello);
+
+///
+/// Tests that the str_ignore_case mode compares decoded string literals ignoring case.
+///
+#[test]
+fn test_str_ignore_case_mode() {
+    assert!(!STRING_CASE);
+    assert!(STR_IGNORED_CASE);
+    assert!(STR_IGNORED_CASE_ESCAPED);
+    assert!(STR_IGNORED_CASE_RAW);
+    assert!(!STR_IGNORED_CASE_CONTENT);
+    assert!(!STR_IGNORED_CASE_NOT_SEMANTIC);
+    assert!(!STR_IGNORED_CASE_IDENTS);
+}
+
+invoke_tt_equal!(BYTE_ESCAPED b'a' b'\x61');
+invoke_tt_equal_in_mode!(SEMANTIC_BYTE_ESCAPED [semantic] b'a' b'\x61');
+invoke_tt_equal_in_mode!(SEMANTIC_BYTE_ESCAPED_QUOTE [semantic] b'\'' b'\x27');
+invoke_tt_equal_in_mode!(SEMANTIC_BYTE_DIFFERENT [semantic] b'a' b'b');
+invoke_tt_equal_in_mode!(SEMANTIC_CHAR_ESCAPED [semantic] 'a' '\x61');
+invoke_tt_equal_in_mode!(SEMANTIC_CHAR_UNICODE_ESCAPED [semantic] 'é' '\u{e9}');
+invoke_tt_equal_in_mode!(SEMANTIC_CHAR_DIFFERENT [semantic] 'a' 'b');
+invoke_tt_equal_in_mode!(SEMANTIC_BYTE_STRING_ESCAPED [semantic] b"ab" b"\x61\x62");
+invoke_tt_equal_in_mode!(SEMANTIC_BYTE_STRING_HIGH_ESCAPE [semantic] b"\xFF" b"\xff");
+invoke_tt_equal_in_mode!(SEMANTIC_BYTE_STRING_DIFFERENT [semantic] b"ab" b"ac");
+invoke_tt_equal_in_mode!(SEMANTIC_BYTE_CHAR [semantic] b'a' 'a');
+invoke_tt_equal_in_mode!(SEMANTIC_BYTE_BYTE_STRING [semantic] b'a' b"a");
+invoke_tt_equal_in_mode!(SEMANTIC_BYTE_STRING_STRING [semantic] b"a" "a");
+
+///
+/// Tests that the semantic mode compares characters, bytes, and byte strings by their values,
+/// but never literals of different kinds.
+///
+#[test]
+fn test_semantic_mode_chars_and_bytes() {
+    assert!(!BYTE_ESCAPED);
+    assert!(SEMANTIC_BYTE_ESCAPED);
+    assert!(SEMANTIC_BYTE_ESCAPED_QUOTE);
+    assert!(!SEMANTIC_BYTE_DIFFERENT);
+    assert!(SEMANTIC_CHAR_ESCAPED);
+    assert!(SEMANTIC_CHAR_UNICODE_ESCAPED);
+    assert!(!SEMANTIC_CHAR_DIFFERENT);
+    assert!(SEMANTIC_BYTE_STRING_ESCAPED);
+    assert!(SEMANTIC_BYTE_STRING_HIGH_ESCAPE);
+    assert!(!SEMANTIC_BYTE_STRING_DIFFERENT);
+    assert!(!SEMANTIC_BYTE_CHAR);
+    assert!(!SEMANTIC_BYTE_BYTE_STRING);
+    assert!(!SEMANTIC_BYTE_STRING_STRING);
+}
+
+invoke_tt_equal!(STATIC_STR {fn f() -> &'static str {}} {fn f() -> &str {}});
+invoke_tt_equal_in_mode!(ELIDED_STATIC_STR [elide_static_str]
+    {fn f() -> &'static str {}} {fn f() -> &str {}});
+invoke_tt_equal_in_mode!(ELIDED_STATIC_STR_NESTED [elide_static_str]
+    {const NAMES: &[&'static str] = &[];} {const NAMES: &[&str] = &[];});
+invoke_tt_equal_in_mode!(ELIDED_STATIC_STR_STRING [elide_static_str] {&'static str} {&String});
+invoke_tt_equal_in_mode!(ELIDED_STATIC_STR_SLICE [elide_static_str] {&'static [u8]} {&[u8]});
+invoke_tt_equal_in_mode!(ELIDED_STATIC_STR_OTHER_LIFETIME [elide_static_str] {&'a str} {&str});
+
+///
+/// Tests that the elide_static_str mode compares `&'static str` and `&str` as equal.
+///
+#[test]
+fn test_elide_static_str_mode() {
+    assert!(!STATIC_STR);
+    assert!(ELIDED_STATIC_STR);
+    assert!(ELIDED_STATIC_STR_NESTED);
+    assert!(!ELIDED_STATIC_STR_STRING);
+    assert!(!ELIDED_STATIC_STR_SLICE);
+    assert!(!ELIDED_STATIC_STR_OTHER_LIFETIME);
+}
+
+invoke_tt_equal!(RAW_BYTE_STRING br"abc" b"abc");
+invoke_tt_equal_in_mode!(SEMANTIC_RAW_BYTE_STRING [semantic] br"abc" b"abc");
+invoke_tt_equal_in_mode!(SEMANTIC_RAW_BYTE_STRING_HASHES [semantic] br#"abc"# br"abc");
+invoke_tt_equal_in_mode!(SEMANTIC_RAW_BYTE_STRING_ESCAPED [semantic] br#"a"b"# b"a\"b");
+invoke_tt_equal_in_mode!(SEMANTIC_RAW_BYTE_STRING_BACKSLASH [semantic] br"a\x62" b"ab");
+invoke_tt_equal_in_mode!(SEMANTIC_RAW_BYTE_STRING_DIFFERENT [semantic] br#"abc"# b"abd");
+invoke_tt_equal_in_mode!(SEMANTIC_RAW_BYTE_STRING_STRING [semantic] br"abc" r"abc");
+
+///
+/// Tests that the semantic mode compares raw byte strings by their bytes.
+///
+#[test]
+fn test_semantic_mode_raw_byte_strings() {
+    assert!(!RAW_BYTE_STRING);
+    assert!(SEMANTIC_RAW_BYTE_STRING);
+    assert!(SEMANTIC_RAW_BYTE_STRING_HASHES);
+    assert!(SEMANTIC_RAW_BYTE_STRING_ESCAPED);
+    assert!(!SEMANTIC_RAW_BYTE_STRING_BACKSLASH);
+    assert!(!SEMANTIC_RAW_BYTE_STRING_DIFFERENT);
+    assert!(!SEMANTIC_RAW_BYTE_STRING_STRING);
+}
+
+invoke_tt_equal!(STRUCT_UPDATE_SPACED {Foo { a: 1, ..base }} {Foo { a: 1, .. base }});
+invoke_tt_equal!(STRUCT_UPDATE_DIFFERENT_BASE {Foo { a: 1, ..x }} {Foo { a: 1, ..y }});
+invoke_tt_equal!(STRUCT_UPDATE_MISSING {Foo { a: 1, ..base }} {Foo { a: 1 }});
+invoke_tt_equal_in_mode!(IGNORED_STRUCT_UPDATE [ignore_struct_update]
+    {Foo { a: 1, ..base }} {Foo { a: 1 }});
+invoke_tt_equal_in_mode!(IGNORED_STRUCT_UPDATE_ONLY [ignore_struct_update]
+    {Foo { ..Default::default() }} {Foo {}});
+invoke_tt_equal_in_mode!(IGNORED_STRUCT_UPDATE_NESTED [ignore_struct_update]
+    {let x = Foo { a: Bar { ..y }, ..z };} {let x = Foo { a: Bar {} };});
+invoke_tt_equal_in_mode!(IGNORED_STRUCT_UPDATE_FIELDS [ignore_struct_update]
+    {Foo { a: 1, ..base }} {Foo { a: 2, ..base }});
+invoke_tt_equal_in_mode!(IGNORED_STRUCT_UPDATE_REST_PATTERN [ignore_struct_update]
+    {let Foo { a, .. } = x;} {let Foo { a } = x;});
+invoke_tt_equal_in_mode!(IGNORED_STRUCT_UPDATE_RANGE [ignore_struct_update] {(a, ..b)} {(a)});
+
+///
+/// Tests that struct updates are compared by default, and ignored in the ignore_struct_update
+/// mode.
+///
+#[test]
+fn test_ignore_struct_update_mode() {
+    assert!(STRUCT_UPDATE_SPACED);
+    assert!(!STRUCT_UPDATE_DIFFERENT_BASE);
+    assert!(!STRUCT_UPDATE_MISSING);
+    assert!(IGNORED_STRUCT_UPDATE);
+    assert!(IGNORED_STRUCT_UPDATE_ONLY);
+    assert!(IGNORED_STRUCT_UPDATE_NESTED);
+    assert!(!IGNORED_STRUCT_UPDATE_FIELDS);
+    assert!(!IGNORED_STRUCT_UPDATE_REST_PATTERN);
+    assert!(!IGNORED_STRUCT_UPDATE_RANGE);
+}
+
+invoke_tt_equal!(FIELD_SHORTHAND {Foo { a }} {Foo { a: a }});
+invoke_tt_equal_in_mode!(NORMALIZED_FIELD_SHORTHAND [normalize_field_shorthand]
+    {Foo { a }} {Foo { a: a }});
+invoke_tt_equal_in_mode!(NORMALIZED_FIELD_SHORTHAND_SEVERAL [normalize_field_shorthand]
+    {Foo { a: a, b: c, d }} {Foo { a, b: c, d: d }});
+invoke_tt_equal_in_mode!(NORMALIZED_FIELD_SHORTHAND_PATTERN [normalize_field_shorthand]
+    {let Foo { a: a, .. } = x;} {let Foo { a, .. } = x;});
+invoke_tt_equal_in_mode!(NORMALIZED_FIELD_SHORTHAND_DIFFERENT [normalize_field_shorthand]
+    {Foo { a }} {Foo { a: b }});
+invoke_tt_equal_in_mode!(NORMALIZED_FIELD_SHORTHAND_EXPRESSION [normalize_field_shorthand]
+    {Foo { a }} {Foo { a: a.b }});
+invoke_tt_equal_in_mode!(NORMALIZED_FIELD_SHORTHAND_PATH [normalize_field_shorthand]
+    {Foo { a }} {Foo { a: a::a }});
+
+///
+/// Tests that the normalize_field_shorthand mode compares shorthand fields as fields
+/// explicitly assigned variables of the same name.
+///
+#[test]
+fn test_normalize_field_shorthand_mode() {
+    assert!(!FIELD_SHORTHAND);
+    assert!(NORMALIZED_FIELD_SHORTHAND);
+    assert!(NORMALIZED_FIELD_SHORTHAND_SEVERAL);
+    assert!(NORMALIZED_FIELD_SHORTHAND_PATTERN);
+    assert!(!NORMALIZED_FIELD_SHORTHAND_DIFFERENT);
+    assert!(!NORMALIZED_FIELD_SHORTHAND_EXPRESSION);
+    assert!(!NORMALIZED_FIELD_SHORTHAND_PATH);
+}
+
+invoke_tt_equal!(PARAM_MUT {fn f(mut x: i32) {}} {fn f(x: i32) {}});
+invoke_tt_equal_in_mode!(IGNORED_PARAM_MUT [ignore_param_mut] {fn f(mut x: i32) {}} {fn f(x: i32) {}});
+invoke_tt_equal_in_mode!(IGNORED_PARAM_MUT_SEVERAL [ignore_param_mut]
+    {fn f<T: Into<u8>>(a: T, mut b: u8, mut self) {}} {fn f<T: Into<u8>>(a: T, b: u8, self) {}});
+invoke_tt_equal_in_mode!(IGNORED_PARAM_MUT_TYPE [ignore_param_mut]
+    {fn f(mut x: i32) {}} {fn f(x: u32) {}});
+invoke_tt_equal_in_mode!(IGNORED_PARAM_MUT_REFERENCE [ignore_param_mut]
+    {fn f(x: &mut i32) {}} {fn f(x: &i32) {}});
+invoke_tt_equal_in_mode!(IGNORED_PARAM_MUT_SELF_REFERENCE [ignore_param_mut]
+    {fn f(&mut self) {}} {fn f(&self) {}});
+invoke_tt_equal_in_mode!(IGNORED_PARAM_MUT_CALL [ignore_param_mut] {{ f(mut x) }} {{ f(x) }});
+
+///
+/// Tests that the ignore_param_mut mode ignores the `mut` of function parameters only.
+///
+#[test]
+fn test_ignore_param_mut_mode() {
+    assert!(!PARAM_MUT);
+    assert!(IGNORED_PARAM_MUT);
+    assert!(IGNORED_PARAM_MUT_SEVERAL);
+    assert!(!IGNORED_PARAM_MUT_TYPE);
+    assert!(!IGNORED_PARAM_MUT_REFERENCE);
+    assert!(!IGNORED_PARAM_MUT_SELF_REFERENCE);
+    assert!(!IGNORED_PARAM_MUT_CALL);
+}
+
+///
+/// Invokes 'tt_equal' with the given `self_type`, producing a bool const of whether the given
+/// input was equal.
+///
+macro_rules! invoke_tt_equal_with_self_type {
+    {
+        $id1:ident [ $($self_type:tt)* ] [ $($input:tt)* ]
+    } => {
+        tt_call! {
+            macro = [{ tt_equal }]
+            input = [{ $($input)* }]
+            self_type = [{ $($self_type)* }]
+            ~~> bool_const! {
+                name = [{ $id1 }]
+            }
+        }
+    }
+}
+
+invoke_tt_equal!(SELF_TYPE {fn new() -> Self} {fn new() -> MyStruct});
+invoke_tt_equal_with_self_type!(SUBSTITUTED_SELF_TYPE [MyStruct]
+    [{fn new() -> Self} {fn new() -> MyStruct}]);
+invoke_tt_equal_with_self_type!(SUBSTITUTED_SELF_TYPE_NESTED [MyStruct]
+    [{impl Foo { fn new() -> Self { Self::default() } }}
+     {impl Foo { fn new() -> MyStruct { MyStruct::default() } }}]);
+invoke_tt_equal_with_self_type!(SUBSTITUTED_SELF_TYPE_GENERIC [Wrapper<u8>]
+    [{fn new() -> Self} {fn new() -> Wrapper<u8>}]);
+invoke_tt_equal_with_self_type!(SUBSTITUTED_SELF_TYPE_SIDE [MyStruct] [Self MyStruct]);
+invoke_tt_equal_with_self_type!(SUBSTITUTED_SELF_TYPE_DIFFERENT [MyStruct]
+    [{fn new() -> Self} {fn new() -> OtherStruct}]);
+invoke_tt_equal_with_self_type!(SUBSTITUTED_SELF_TYPE_LOWERCASE [MyStruct]
+    [{fn f(self)} {fn f(MyStruct)}]);
+
+///
+/// Tests that `Self` is replaced by `self_type` when given.
+///
+#[test]
+fn test_self_type() {
+    assert!(!SELF_TYPE);
+    assert!(SUBSTITUTED_SELF_TYPE);
+    assert!(SUBSTITUTED_SELF_TYPE_NESTED);
+    assert!(SUBSTITUTED_SELF_TYPE_GENERIC);
+    assert!(SUBSTITUTED_SELF_TYPE_SIDE);
+    assert!(!SUBSTITUTED_SELF_TYPE_DIFFERENT);
+    assert!(!SUBSTITUTED_SELF_TYPE_LOWERCASE);
+}
+
+invoke_tt_equal_in_mode!(ALPHA_RENAMED_GENERICS [alpha_rename_generics]
+    {impl<T> Trait<T> for Foo<T> {}} {impl<U> Trait<U> for Foo<U> {}});
+invoke_tt_equal_in_mode!(ALPHA_RENAMED_GENERICS_BODY [alpha_rename_generics]
+    {impl<T: Clone> Trait<T> for Foo<T> { fn f(t: T) -> T { t } }}
+    {impl<U: Clone> Trait<U> for Foo<U> { fn f(t: U) -> U { t } }});
+invoke_tt_equal_in_mode!(ALPHA_RENAMED_GENERICS_STRUCTURE [alpha_rename_generics]
+    {impl<T> Trait<T> for Foo<T> {}} {impl<U> Trait<U> for Foo<Vec<U>> {}});
+invoke_tt_equal_in_mode!(ALPHA_RENAMED_GENERICS_ORDER [alpha_rename_generics]
+    {impl<T, U> Trait<T> for Foo<U> {}} {impl<T, U> Trait<U> for Foo<T> {}});
+invoke_tt_equal_in_mode!(ALPHA_RENAMED_GENERICS_FREE [alpha_rename_generics]
+    {impl<T> Trait<T> for Foo<T> {}} {impl<U> Trait<V> for Foo<U> {}});
+
+///
+/// Tests that the alpha_rename_generics alias of the ignore_impl_generics_names mode compares
+/// alpha-equivalent impl blocks as equal.
+///
+#[test]
+fn test_alpha_rename_generics_mode() {
+    assert!(ALPHA_RENAMED_GENERICS);
+    assert!(ALPHA_RENAMED_GENERICS_BODY);
+    assert!(!ALPHA_RENAMED_GENERICS_STRUCTURE);
+    assert!(!ALPHA_RENAMED_GENERICS_ORDER);
+    assert!(!ALPHA_RENAMED_GENERICS_FREE);
+}
+
+invoke_tt_equal!(ANON_LIFETIME {Foo<'_>} {Foo<'a>});
+invoke_tt_equal_in_mode!(ANON_LIFETIME_WILDCARD [anon_lifetime_wildcard] {Foo<'_>} {Foo<'a>});
+invoke_tt_equal_in_mode!(ANON_LIFETIME_WILDCARD_RIGHT [anon_lifetime_wildcard]
+    {fn f(x: &'a str) -> Foo<'a>} {fn f(x: &'_ str) -> Foo<'_>});
+invoke_tt_equal_in_mode!(ANON_LIFETIME_WILDCARD_BOTH [anon_lifetime_wildcard] {Foo<'_>} {Foo<'_>});
+invoke_tt_equal_in_mode!(ANON_LIFETIME_WILDCARD_NAMED [anon_lifetime_wildcard] {Foo<'a>} {Foo<'b>});
+invoke_tt_equal_in_mode!(ANON_LIFETIME_WILDCARD_TYPE [anon_lifetime_wildcard] {Foo<'_>} {Foo<T>});
+invoke_tt_equal_in_mode!(ANON_LIFETIME_WILDCARD_MISSING [anon_lifetime_wildcard] {&'_ str} {&str});
+
+///
+/// Tests that the anon_lifetime_wildcard mode lets `'_` match any lifetime, but only lifetimes.
+///
+#[test]
+fn test_anon_lifetime_wildcard_mode() {
+    assert!(!ANON_LIFETIME);
+    assert!(ANON_LIFETIME_WILDCARD);
+    assert!(ANON_LIFETIME_WILDCARD_RIGHT);
+    assert!(ANON_LIFETIME_WILDCARD_BOTH);
+    assert!(!ANON_LIFETIME_WILDCARD_NAMED);
+    assert!(!ANON_LIFETIME_WILDCARD_TYPE);
+    assert!(!ANON_LIFETIME_WILDCARD_MISSING);
+}
+
+invoke_tt_equal!(LET_MUT {let mut x = 1;} {let x = 1;});
+invoke_tt_equal_in_mode!(IGNORED_LET_MUT [ignore_let_mut] {let mut x = 1;} {let x = 1;});
+invoke_tt_equal_in_mode!(IGNORED_LET_MUT_NESTED [ignore_let_mut]
+    {fn f() { let mut x = 1; }} {fn f() { let x = 1; }});
+invoke_tt_equal_in_mode!(IGNORED_LET_MUT_NAMES [ignore_let_mut] {let mut x = 1;} {let mut y = 1;});
+invoke_tt_equal_in_mode!(IGNORED_LET_MUT_REFERENCE [ignore_let_mut] {let x = &mut y;} {let x = &y;});
+invoke_tt_equal_in_mode!(IGNORED_LET_MUT_PATTERN [ignore_let_mut]
+    {let (mut a, b) = c;} {let (a, b) = c;});
+invoke_tt_equal_in_mode!(IGNORED_LET_MUT_PARAM [ignore_let_mut] {fn f(mut x: u8) {}} {fn f(x: u8) {}});
+
+///
+/// Tests that the ignore_let_mut mode ignores only the `mut` directly following `let`.
+///
+#[test]
+fn test_ignore_let_mut_mode() {
+    assert!(!LET_MUT);
+    assert!(IGNORED_LET_MUT);
+    assert!(IGNORED_LET_MUT_NESTED);
+    assert!(!IGNORED_LET_MUT_NAMES);
+    assert!(!IGNORED_LET_MUT_REFERENCE);
+    assert!(!IGNORED_LET_MUT_PATTERN);
+    assert!(!IGNORED_LET_MUT_PARAM);
+}
+
+invoke_tt_equal!(RESULT_ERR {Result<u8, X>} {Result<u8, Y>});
+invoke_tt_equal_in_mode!(IGNORED_RESULT_ERR [ignore_result_err] {Result<u8, X>} {Result<u8, Y>});
+invoke_tt_equal_in_mode!(IGNORED_RESULT_ERR_SIGNATURE [ignore_result_err]
+    {fn f() -> std::result::Result<Vec<u8>, Box<dyn Error>> {}} {fn f() -> std::result::Result<Vec<u8>, io::Error> {}});
+invoke_tt_equal_in_mode!(IGNORED_RESULT_ERR_NESTED [ignore_result_err]
+    {Result<Result<u8, A>, B>} {Result<Result<u8, C>, D>});
+invoke_tt_equal_in_mode!(IGNORED_RESULT_ERR_OK [ignore_result_err] {Result<u8, X>} {Result<u16, X>});
+invoke_tt_equal_in_mode!(IGNORED_RESULT_ERR_ALIAS [ignore_result_err] {Result<u8>} {Result<u8, X>});
+invoke_tt_equal_in_mode!(IGNORED_RESULT_ERR_OTHER_NAME [ignore_result_err]
+    {MyResult<u8, X>} {MyResult<u8, Y>});
+
+///
+/// Tests that the ignore_result_err mode ignores the error type of `Result<T, E>` only.
+///
+#[test]
+fn test_ignore_result_err_mode() {
+    assert!(!RESULT_ERR);
+    assert!(IGNORED_RESULT_ERR);
+    assert!(IGNORED_RESULT_ERR_SIGNATURE);
+    assert!(IGNORED_RESULT_ERR_NESTED);
+    assert!(!IGNORED_RESULT_ERR_OK);
+    assert!(!IGNORED_RESULT_ERR_ALIAS);
+    assert!(!IGNORED_RESULT_ERR_OTHER_NAME);
+}
+
+invoke_tt_equal!(UNSAFE_BLOCK {unsafe { foo() }} {foo()});
+invoke_tt_equal_in_mode!(IGNORED_UNSAFE_BLOCK [ignore_unsafe_blocks] {unsafe { foo() }} {foo()});
+invoke_tt_equal_in_mode!(IGNORED_UNSAFE_BLOCK_NESTED [ignore_unsafe_blocks]
+    {let x = unsafe { unsafe { foo() } };} {let x = foo();});
+invoke_tt_equal_in_mode!(IGNORED_UNSAFE_BLOCK_DESUGARED [ignore_unsafe_blocks desugar_try]
+    {unsafe { match foo() { Ok(v) => v, Err(e) => return Err(e.into()) } }} {foo()?});
+invoke_tt_equal_in_mode!(IGNORED_UNSAFE_BLOCK_DIFFERENT [ignore_unsafe_blocks]
+    {unsafe { foo() }} {bar()});
+invoke_tt_equal_in_mode!(IGNORED_UNSAFE_BLOCK_FN [ignore_unsafe_blocks]
+    {unsafe fn f() {}} {fn f() {}});
+invoke_tt_equal_in_mode!(IGNORED_UNSAFE_BLOCK_IMPL [ignore_unsafe_blocks]
+    {unsafe impl Send for Foo {}} {impl Send for Foo {}});
+
+///
+/// Tests that the ignore_unsafe_blocks mode compares `unsafe` blocks as their contents.
+///
+#[test]
+fn test_ignore_unsafe_blocks_mode() {
+    assert!(!UNSAFE_BLOCK);
+    assert!(IGNORED_UNSAFE_BLOCK);
+    assert!(IGNORED_UNSAFE_BLOCK_NESTED);
+    assert!(IGNORED_UNSAFE_BLOCK_DESUGARED);
+    assert!(!IGNORED_UNSAFE_BLOCK_DIFFERENT);
+    assert!(!IGNORED_UNSAFE_BLOCK_FN);
+    assert!(!IGNORED_UNSAFE_BLOCK_IMPL);
+}
+
+invoke_tt_equal!(UNIT_STRUCT {struct Foo;} {struct Foo {}});
+invoke_tt_equal_in_mode!(UNIT_STRUCT_FORMS [unit_struct_forms] {struct Foo;} {struct Foo {}});
+invoke_tt_equal_in_mode!(UNIT_STRUCT_FORMS_GENERIC [unit_struct_forms]
+    {pub struct Foo<T: Into<u8>>; struct Bar;} {pub struct Foo<T: Into<u8>> {} struct Bar {}});
+invoke_tt_equal_in_mode!(UNIT_STRUCT_FORMS_FIELDS [unit_struct_forms]
+    {struct Foo;} {struct Foo { x: u8 }});
+invoke_tt_equal_in_mode!(UNIT_STRUCT_FORMS_LITERAL [unit_struct_forms] {let x = Foo {};} {let x = Foo;});
+invoke_tt_equal_in_mode!(UNIT_STRUCT_FORMS_FN [unit_struct_forms] {fn foo() {}} {fn foo();});
+
+///
+/// Tests that the unit_struct_forms mode compares the `;` and `{}` bodies of unit structs
+/// as equal, but nothing else.
+///
+#[test]
+fn test_unit_struct_forms_mode() {
+    assert!(!UNIT_STRUCT);
+    assert!(UNIT_STRUCT_FORMS);
+    assert!(UNIT_STRUCT_FORMS_GENERIC);
+    assert!(!UNIT_STRUCT_FORMS_FIELDS);
+    assert!(!UNIT_STRUCT_FORMS_LITERAL);
+    assert!(!UNIT_STRUCT_FORMS_FN);
+}
+
+invoke_tt_equal!(STATIC_BOUND {fn f<T: 'static>() {}} {fn f<T>() {}});
+invoke_tt_equal_in_mode!(IGNORED_STATIC_BOUND [ignore_static_bounds]
+    {fn f<T: 'static>() {}} {fn f<T>() {}});
+invoke_tt_equal_in_mode!(IGNORED_STATIC_BOUND_OTHER_LIFETIME [ignore_static_bounds]
+    {fn f<T: 'a>() {}} {fn f<T>() {}});
+invoke_tt_equal_in_mode!(IGNORED_STATIC_BOUND_LAST [ignore_static_bounds]
+    {fn f<T: Clone + 'static, U>() {}} {fn f<T: Clone, U>() {}});
+invoke_tt_equal_in_mode!(IGNORED_STATIC_BOUND_FIRST [ignore_static_bounds]
+    {impl<T: 'static + Clone> Foo for Bar<T> {}} {impl<T: Clone> Foo for Bar<T> {}});
+invoke_tt_equal_in_mode!(IGNORED_STATIC_BOUND_ONLY_STATIC [ignore_static_bounds]
+    {struct Foo<T: 'static + 'a>(T);} {struct Foo<T>(T);});
+invoke_tt_equal_in_mode!(IGNORED_STATIC_BOUND_DEFAULT [ignore_static_bounds]
+    {struct Foo<T = Box<dyn Any + 'static>>(T);} {struct Foo<T = Box<dyn Any>>(T);});
+invoke_tt_equal_in_mode!(IGNORED_STATIC_BOUND_TYPE [ignore_static_bounds]
+    {fn f(x: &'static str) {}} {fn f(x: &str) {}});
+
+///
+/// Tests that the ignore_static_bounds mode ignores `'static` bounds of generic parameters,
+/// but no other lifetimes.
+///
+#[test]
+fn test_ignore_static_bounds_mode() {
+    assert!(!STATIC_BOUND);
+    assert!(IGNORED_STATIC_BOUND);
+    assert!(!IGNORED_STATIC_BOUND_OTHER_LIFETIME);
+    assert!(IGNORED_STATIC_BOUND_LAST);
+    assert!(IGNORED_STATIC_BOUND_FIRST);
+    assert!(!IGNORED_STATIC_BOUND_ONLY_STATIC);
+    assert!(!IGNORED_STATIC_BOUND_DEFAULT);
+    assert!(!IGNORED_STATIC_BOUND_TYPE);
+}
@@ -0,0 +1,51 @@
+#![allow(clippy::assertions_on_constants)]
+
+use tt_call::tt_if;
+use tt_equal::tt_all_equal;
+
+///
+/// We use this macro to invoke 'tt_all_equal' and produce a bool const of whether all the
+/// given token trees are equal.
+///
+/// The first argument is the name of the resulting const, while the rest are to be compared.
+///
+macro_rules! invoke_tt_all_equal {
+    {
+        $id:ident $($tt:tt)*
+    } => {
+        tt_if!{
+            condition = [{tt_all_equal}]
+            input = [{ $($tt)* }]
+            true = [{
+                const $id: bool = true;
+            }]
+            false = [{
+                const $id: bool = false;
+            }]
+        }
+    }
+}
+
+invoke_tt_all_equal!(THREE_EQUAL a a a);
+invoke_tt_all_equal!(LAST_DIFFERENT a a b);
+invoke_tt_all_equal!(FIRST_DIFFERENT b a a);
+invoke_tt_all_equal!(SINGLE a);
+invoke_tt_all_equal!(TWO_EQUAL a a);
+invoke_tt_all_equal!(FIVE_GROUPS(a, b)(a, b)(a, b)(a, b)(a, b));
+invoke_tt_all_equal!(OPERATORS += += +=);
+invoke_tt_all_equal!(SPLIT_OPERATOR += += + =);
+
+///
+/// Tests that `tt_all_equal` returns whether all the token trees equal the first.
+///
+#[test]
+fn test_tt_all_equal_invocations() {
+    assert!(THREE_EQUAL);
+    assert!(!LAST_DIFFERENT);
+    assert!(!FIRST_DIFFERENT);
+    assert!(SINGLE);
+    assert!(TWO_EQUAL);
+    assert!(FIVE_GROUPS);
+    assert!(OPERATORS);
+    assert!(!SPLIT_OPERATOR);
+}
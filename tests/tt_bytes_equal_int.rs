@@ -0,0 +1,61 @@
+#![allow(clippy::assertions_on_constants)]
+
+use tt_call::tt_call;
+use tt_equal::tt_bytes_equal_int;
+
+///
+/// Produces a bool const with the given name and value.
+/// Used as the return destination of 'tt_bytes_equal_int'.
+///
+macro_rules! bool_const {
+    {
+        name = [{ $name:ident }]
+        is_equal = [{ $is_equal:tt }]
+    } => {
+        const $name: bool = $is_equal;
+    }
+}
+
+///
+/// We use this macro to invoke 'tt_bytes_equal_int' and produce a bool const of whether the
+/// integer equals the byte array in the given endianness.
+///
+macro_rules! invoke_tt_bytes_equal_int {
+    {
+        $id:ident $int:tt $bytes:tt $endian:ident
+    } => {
+        tt_call! {
+            macro = [{ tt_bytes_equal_int }]
+            input = [{ $int $bytes }]
+            endian = [{ $endian }]
+            ~~> bool_const! {
+                name = [{ $id }]
+            }
+        }
+    }
+}
+
+invoke_tt_bytes_equal_int!(LE_MATCH 0x12345678 [0x78, 0x56, 0x34, 0x12] le);
+invoke_tt_bytes_equal_int!(LE_REVERSED 0x12345678 [0x12, 0x34, 0x56, 0x78] le);
+invoke_tt_bytes_equal_int!(BE_MATCH 0x12345678 [0x12, 0x34, 0x56, 0x78] be);
+invoke_tt_bytes_equal_int!(BE_REVERSED 0x12345678 [0x78, 0x56, 0x34, 0x12] be);
+invoke_tt_bytes_equal_int!(DECIMAL_TRAILING_COMMA 305_419_896u32 [120, 86, 52, 18,] le);
+invoke_tt_bytes_equal_int!(ZERO_PADDED 0x1234 [0x34, 0x12, 0x00, 0x00] le);
+invoke_tt_bytes_equal_int!(TOO_WIDE 0x12345678 [0x78, 0x56] le);
+invoke_tt_bytes_equal_int!(EMPTY_ZERO 0 [] be);
+
+///
+/// Tests that `tt_bytes_equal_int` compares integers against their byte representation in
+/// both little and big endian.
+///
+#[test]
+fn test_tt_bytes_equal_int_invocations() {
+    assert!(LE_MATCH);
+    assert!(!LE_REVERSED);
+    assert!(BE_MATCH);
+    assert!(!BE_REVERSED);
+    assert!(DECIMAL_TRAILING_COMMA);
+    assert!(ZERO_PADDED);
+    assert!(!TOO_WIDE);
+    assert!(EMPTY_ZERO);
+}
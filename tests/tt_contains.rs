@@ -0,0 +1,54 @@
+#![allow(clippy::assertions_on_constants)]
+
+use tt_call::tt_if;
+use tt_equal::tt_contains;
+
+///
+/// We use this macro to invoke 'tt_contains' and produce a bool const of whether the
+/// haystack contains the needle.
+///
+/// The first argument is the name of the resulting const, the second is the haystack group,
+/// while the third is the needle group.
+///
+macro_rules! invoke_tt_contains {
+    {
+        $id:ident $haystack:tt $needle:tt
+    } => {
+        tt_if!{
+            condition = [{tt_contains}]
+            input = [{ $haystack $needle }]
+            true = [{
+                const $id: bool = true;
+            }]
+            false = [{
+                const $id: bool = false;
+            }]
+        }
+    }
+}
+
+invoke_tt_contains!(CONTAINS_MIDDLE (a b c d) (b c));
+invoke_tt_contains!(CONTAINS_END (a b c d) (c d));
+invoke_tt_contains!(CONTAINS_WHOLE (a b c d) [a b c d]);
+invoke_tt_contains!(CONTAINS_NOT_CONSECUTIVE (a b c d) (b d));
+invoke_tt_contains!(CONTAINS_LONGER_NEEDLE (a b) (a b c));
+invoke_tt_contains!(CONTAINS_EMPTY_NEEDLE (a b) ());
+invoke_tt_contains!(CONTAINS_EMPTY_BOTH()());
+invoke_tt_contains!(CONTAINS_GROUP(f(x, y) + 1)((x, y) + 1));
+invoke_tt_contains!(CONTAINS_PART_OF_OPERATOR (a += b) (=));
+
+///
+/// Tests that `tt_contains` returns whether the needle occurs consecutively in the haystack.
+///
+#[test]
+fn test_tt_contains_invocations() {
+    assert!(CONTAINS_MIDDLE);
+    assert!(CONTAINS_END);
+    assert!(CONTAINS_WHOLE);
+    assert!(!CONTAINS_NOT_CONSECUTIVE);
+    assert!(!CONTAINS_LONGER_NEEDLE);
+    assert!(CONTAINS_EMPTY_NEEDLE);
+    assert!(CONTAINS_EMPTY_BOTH);
+    assert!(CONTAINS_GROUP);
+    assert!(!CONTAINS_PART_OF_OPERATOR);
+}
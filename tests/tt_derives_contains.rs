@@ -0,0 +1,61 @@
+#![allow(clippy::assertions_on_constants)]
+
+use tt_call::tt_call;
+use tt_equal::tt_derives_contains;
+
+///
+/// Produces a bool const with the given name and value.
+/// Used as the return destination of 'tt_derives_contains'.
+///
+macro_rules! bool_const {
+    {
+        name = [{ $name:ident }]
+        contains = [{ $contains:tt }]
+    } => {
+        const $name: bool = $contains;
+    }
+}
+
+///
+/// We use this macro to invoke 'tt_derives_contains' and produce a bool const of whether the
+/// given derive attribute derives all the required traits.
+///
+macro_rules! invoke_tt_derives_contains {
+    {
+        $id:ident $attr:tt [ $($required:tt)* ]
+    } => {
+        tt_call! {
+            macro = [{ tt_derives_contains }]
+            input = [{ # $attr }]
+            required = [{ $($required)* }]
+            ~~> bool_const! {
+                name = [{ $id }]
+            }
+        }
+    }
+}
+
+invoke_tt_derives_contains!(ALL_PRESENT [derive(Debug, Clone)] [Debug, Clone]);
+invoke_tt_derives_contains!(REORDERED [derive(Debug, Clone)] [Clone, Debug]);
+invoke_tt_derives_contains!(EXTRA_DERIVED[derive(Debug, Clone, Hash)][Clone]);
+invoke_tt_derives_contains!(MISSING [derive(Debug, Clone)] [Clone, Hash]);
+invoke_tt_derives_contains!(NONE_REQUIRED [derive(Debug)] []);
+invoke_tt_derives_contains!(TRAILING_COMMAS [derive(Debug, Clone,)] [Debug,]);
+invoke_tt_derives_contains!(PATHS[derive(std::fmt::Debug, Clone)][std::fmt::Debug]);
+invoke_tt_derives_contains!(DIFFERENT_PATH[derive(std::fmt::Debug)][Debug]);
+
+///
+/// Tests that `tt_derives_contains` checks for the required traits regardless of order,
+/// allowing additional derived traits.
+///
+#[test]
+fn test_tt_derives_contains_invocations() {
+    assert!(ALL_PRESENT);
+    assert!(REORDERED);
+    assert!(EXTRA_DERIVED);
+    assert!(!MISSING);
+    assert!(NONE_REQUIRED);
+    assert!(TRAILING_COMMAS);
+    assert!(PATHS);
+    assert!(!DIFFERENT_PATH);
+}
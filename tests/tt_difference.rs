@@ -0,0 +1,56 @@
+use tt_call::tt_call;
+use tt_equal::tt_difference;
+
+///
+/// Produces a const with the given name holding the stringified differences.
+/// Used as the return destination of 'tt_difference'.
+///
+macro_rules! differences_const {
+    {
+        name = [{ $name:ident }]
+        only_left = [{ $($only_left:tt)* }]
+        only_right = [{ $($only_right:tt)* }]
+    } => {
+        const $name: (&str, &str) = (stringify!($($only_left)*), stringify!($($only_right)*));
+    }
+}
+
+///
+/// We use this macro to invoke 'tt_difference' and produce a const of the stringified
+/// differences between the two given sequences.
+///
+macro_rules! invoke_tt_difference {
+    {
+        $id:ident $lhs:tt $rhs:tt
+    } => {
+        tt_call! {
+            macro = [{ tt_difference }]
+            input = [{ $lhs $rhs }]
+            ~~> differences_const! {
+                name = [{ $id }]
+            }
+        }
+    }
+}
+
+invoke_tt_difference!(EQUAL(a, b, c)(c, b, a));
+invoke_tt_difference!(OVERLAPPING(a, b, c)(b, d, a));
+invoke_tt_difference!(DISJOINT(a, b)(c, d));
+invoke_tt_difference!(DUPLICATES(a, a, b)(a, b, b));
+invoke_tt_difference!(MULTI_TOKEN_ELEMENTS (x: u8, y: u16) (y: u16, x: u32));
+invoke_tt_difference!(EMPTY_LEFT()(a, b));
+
+///
+/// Tests that `tt_difference` returns the elements of each sequence missing from the other.
+///
+#[test]
+fn test_tt_difference_invocations() {
+    assert_eq!(EQUAL, ("", ""));
+    assert_eq!(OVERLAPPING, ("c", "d"));
+    assert_eq!(DISJOINT, ("a, b", "c, d"));
+    assert_eq!(DUPLICATES, ("a", "b"));
+    // Stringification may space the tokens differently than they were written
+    assert_eq!(MULTI_TOKEN_ELEMENTS.0.replace(' ', ""), "x:u8");
+    assert_eq!(MULTI_TOKEN_ELEMENTS.1.replace(' ', ""), "x:u32");
+    assert_eq!(EMPTY_LEFT, ("", "a, b"));
+}
@@ -0,0 +1,52 @@
+#![allow(clippy::assertions_on_constants)]
+
+use tt_call::tt_if;
+use tt_equal::tt_equal_any;
+
+///
+/// We use this macro to invoke 'tt_equal_any' and produce a bool const of whether the
+/// given token tree equals any of the given alternatives.
+///
+/// The first argument is the name of the resulting const, the second is the token tree
+/// to look for, while the third is the group of alternatives.
+///
+macro_rules! invoke_tt_equal_any {
+    {
+        $id:ident $needle:tt $alternatives:tt
+    } => {
+        tt_if!{
+            condition = [{tt_equal_any}]
+            input = [{ $needle $alternatives }]
+            true = [{
+                const $id: bool = true;
+            }]
+            false = [{
+                const $id: bool = false;
+            }]
+        }
+    }
+}
+
+invoke_tt_equal_any!(MATCHES_SECOND b (a b c));
+invoke_tt_equal_any!(MATCHES_NONE d (a b c));
+invoke_tt_equal_any!(MATCHES_FIRST a [a b c]);
+invoke_tt_equal_any!(NO_ALTERNATIVES a ());
+invoke_tt_equal_any!(MATCHES_GROUP (x, y) ((x) (x, y) [x, y]));
+invoke_tt_equal_any!(MATCHES_NO_GROUP (x, y) ((x) [x, y] {x, y}));
+invoke_tt_equal_any!(MATCHES_OPERATOR += {+ = += -=});
+invoke_tt_equal_any!(MATCHES_NO_OPERATOR += {+ = -=});
+
+///
+/// Tests that `tt_equal_any` returns whether any alternative equals the token tree.
+///
+#[test]
+fn test_tt_equal_any_invocations() {
+    assert!(MATCHES_SECOND);
+    assert!(!MATCHES_NONE);
+    assert!(MATCHES_FIRST);
+    assert!(!NO_ALTERNATIVES);
+    assert!(MATCHES_GROUP);
+    assert!(!MATCHES_NO_GROUP);
+    assert!(MATCHES_OPERATOR);
+    assert!(!MATCHES_NO_OPERATOR);
+}
@@ -0,0 +1,61 @@
+use tt_call::tt_call;
+use tt_equal::tt_first_diff;
+
+///
+/// We use this macro to invoke 'tt_first_diff' and produce an `Option<usize>` const of the
+/// index where the two given groups first differ.
+///
+/// The first argument is the name of the resulting const, while the following two
+/// are the groups to compare.
+///
+macro_rules! invoke_tt_first_diff {
+    {
+        $id:ident $lhs:tt $rhs:tt
+    } => {
+        tt_call!{
+            macro = [{ tt_first_diff }]
+            input = [{ $lhs $rhs }]
+            ~~> first_diff_const!{ $id }
+        }
+    }
+}
+
+///
+/// Produces an `Option<usize>` const with the given name from the output of `tt_first_diff`.
+///
+macro_rules! first_diff_const {
+    {
+        $id:ident
+        diff_index = [{ none }]
+    } => {
+        const $id: Option<usize> = None;
+    };
+    {
+        $id:ident
+        diff_index = [{ $index:literal }]
+    } => {
+        const $id: Option<usize> = Some($index);
+    };
+}
+
+invoke_tt_first_diff!(DIFF_MIDDLE (a b c) (a x c));
+invoke_tt_first_diff!(DIFF_FIRST (a b c) [x b c]);
+invoke_tt_first_diff!(DIFF_NONE (a b c) (a b c));
+invoke_tt_first_diff!(DIFF_NONE_EMPTY () {});
+invoke_tt_first_diff!(DIFF_NESTED (a (b c) d) (a (b x) d));
+invoke_tt_first_diff!(DIFF_OPERATOR(a += b -= c)(a += b = c));
+invoke_tt_first_diff!(DIFF_SHORTER (a b) (a b c));
+
+///
+/// Tests that `tt_first_diff` returns the index of the first differing token tree.
+///
+#[test]
+fn test_tt_first_diff_invocations() {
+    assert_eq!(DIFF_MIDDLE, Some(1));
+    assert_eq!(DIFF_FIRST, Some(0));
+    assert_eq!(DIFF_NONE, None);
+    assert_eq!(DIFF_NONE_EMPTY, None);
+    assert_eq!(DIFF_NESTED, Some(1));
+    assert_eq!(DIFF_OPERATOR, Some(3));
+    assert_eq!(DIFF_SHORTER, Some(2));
+}
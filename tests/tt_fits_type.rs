@@ -0,0 +1,58 @@
+#![allow(clippy::assertions_on_constants)]
+
+use tt_call::tt_if;
+use tt_equal::tt_fits_type;
+
+///
+/// We use this macro to invoke 'tt_fits_type' and produce a bool const of whether the
+/// given literal fits in the given type.
+///
+/// The first argument is the name of the resulting const, while the rest is the input to
+/// 'tt_fits_type'.
+///
+macro_rules! invoke_tt_fits_type {
+    {
+        $id:ident $($input:tt)*
+    } => {
+        tt_if!{
+            condition = [{tt_fits_type}]
+            input = [{ $($input)* }]
+            true = [{
+                const $id: bool = true;
+            }]
+            false = [{
+                const $id: bool = false;
+            }]
+        }
+    }
+}
+
+invoke_tt_fits_type!(U8_MAX 255 u8);
+invoke_tt_fits_type!(U8_MAX_HEX 0xFF u8);
+invoke_tt_fits_type!(U8_OVERFLOW 256 u8);
+invoke_tt_fits_type!(U8_NEGATIVE -1 u8);
+invoke_tt_fits_type!(U8_NEGATIVE_ZERO -0 u8);
+invoke_tt_fits_type!(I8_MIN -128 i8);
+invoke_tt_fits_type!(I8_UNDERFLOW -129 i8);
+invoke_tt_fits_type!(I8_OVERFLOW 128 i8);
+invoke_tt_fits_type!(SUFFIX_IGNORED 300u32 u16);
+invoke_tt_fits_type!(U128_MAX 340_282_366_920_938_463_463_374_607_431_768_211_455 u128);
+invoke_tt_fits_type!(I128_MIN -170_141_183_460_469_231_731_687_303_715_884_105_728 i128);
+
+///
+/// Tests that `tt_fits_type` checks literals against the range of each integer type.
+///
+#[test]
+fn test_tt_fits_type_invocations() {
+    assert!(U8_MAX);
+    assert!(U8_MAX_HEX);
+    assert!(!U8_OVERFLOW);
+    assert!(!U8_NEGATIVE);
+    assert!(U8_NEGATIVE_ZERO);
+    assert!(I8_MIN);
+    assert!(!I8_UNDERFLOW);
+    assert!(!I8_OVERFLOW);
+    assert!(SUFFIX_IGNORED);
+    assert!(U128_MAX);
+    assert!(I128_MIN);
+}
@@ -0,0 +1,54 @@
+#![allow(clippy::assertions_on_constants)]
+
+use tt_call::tt_if;
+use tt_equal::tt_multiset_equal;
+
+///
+/// We use this macro to invoke 'tt_multiset_equal' and produce a bool const of whether the
+/// two given sequences contain the same elements.
+///
+/// The first argument is the name of the resulting const, while the two following arguments
+/// are the sequences to be compared.
+///
+macro_rules! invoke_tt_multiset_equal {
+    {
+        $id:ident $lhs:tt $rhs:tt
+    } => {
+        tt_if!{
+            condition = [{tt_multiset_equal}]
+            input = [{ $lhs $rhs }]
+            true = [{
+                const $id: bool = true;
+            }]
+            false = [{
+                const $id: bool = false;
+            }]
+        }
+    }
+}
+
+invoke_tt_multiset_equal!(SAME_ORDER (a, b, c) (a, b, c));
+invoke_tt_multiset_equal!(REORDERED (a, b, c) (c, a, b));
+invoke_tt_multiset_equal!(REORDERED_DUPLICATES (a, b, a) (b, a, a));
+invoke_tt_multiset_equal!(DIFFERENT_COUNTS (a, b, a) (a, b, b));
+invoke_tt_multiset_equal!(MISSING_DUPLICATE (a, b, a) (a, b));
+invoke_tt_multiset_equal!(DIFFERENT_TOKENS (a, b) (a, c));
+invoke_tt_multiset_equal!(MULTI_TOKEN_ELEMENTS (u8: 1, &str: 2) (&str: 2, u8: 1));
+invoke_tt_multiset_equal!(TRAILING_COMMA (a, b,) [b, a]);
+invoke_tt_multiset_equal!(EMPTY () ());
+
+///
+/// Tests that `tt_multiset_equal` ignores the order of elements but not how often they occur.
+///
+#[test]
+fn test_tt_multiset_equal_invocations() {
+    assert!(SAME_ORDER);
+    assert!(REORDERED);
+    assert!(REORDERED_DUPLICATES);
+    assert!(!DIFFERENT_COUNTS);
+    assert!(!MISSING_DUPLICATE);
+    assert!(!DIFFERENT_TOKENS);
+    assert!(MULTI_TOKEN_ELEMENTS);
+    assert!(TRAILING_COMMA);
+    assert!(EMPTY);
+}
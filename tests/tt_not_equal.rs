@@ -0,0 +1,56 @@
+#![allow(clippy::assertions_on_constants)]
+
+use tt_call::tt_if;
+use tt_equal::tt_not_equal;
+
+///
+/// We use this macro to invoke 'tt_not_equal' and produce a bool const of whether the
+/// two given tokens were not equal.
+///
+/// The first argument is the name of the resulting const, while the two following arguments
+/// are to be compared.
+///
+macro_rules! invoke_tt_not_equal {
+    {
+        $id1:ident $tt1:tt $tt2:tt
+    } => {
+        tt_if!{
+            condition = [{tt_not_equal}]
+            input = [{ $tt1 $tt2 }]
+            true = [{
+                const $id1: bool = true;
+            }]
+            false = [{
+                const $id1: bool = false;
+            }]
+        }
+    }
+}
+
+invoke_tt_not_equal!(COLONS : :);
+invoke_tt_not_equal!(COLON_EQUAL : =);
+invoke_tt_not_equal!(DOUBLE_SINGLE_COLON :: :);
+invoke_tt_not_equal!(SINGLE_DOUBLE_COLON : ::);
+invoke_tt_not_equal!(DOUBLE_DOUBLE_COLON :: ::);
+invoke_tt_not_equal!(INCLUSIVE_RANGE_DOUBLE_COLON ..= ::);
+invoke_tt_not_equal!(IDENTS a a);
+invoke_tt_not_equal!(DIFFERENT_IDENTS a b);
+invoke_tt_not_equal!(GROUPS(a, b)(a, b));
+invoke_tt_not_equal!(DIFFERENT_GROUPS (a b) [a b]);
+
+///
+/// Tests that `tt_not_equal` produces the negated equality result for all invocations.
+///
+#[test]
+fn test_tt_not_equal_invocations() {
+    assert!(!COLONS);
+    assert!(COLON_EQUAL);
+    assert!(DOUBLE_SINGLE_COLON);
+    assert!(SINGLE_DOUBLE_COLON);
+    assert!(!DOUBLE_DOUBLE_COLON);
+    assert!(INCLUSIVE_RANGE_DOUBLE_COLON);
+    assert!(!IDENTS);
+    assert!(DIFFERENT_IDENTS);
+    assert!(!GROUPS);
+    assert!(DIFFERENT_GROUPS);
+}
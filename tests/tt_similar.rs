@@ -0,0 +1,67 @@
+#![allow(clippy::assertions_on_constants)]
+
+use tt_call::tt_call;
+use tt_equal::tt_similar;
+
+///
+/// Produces a bool const with the given name and value.
+/// Used as the return destination of 'tt_similar'.
+///
+macro_rules! bool_const {
+    {
+        name = [{ $name:ident }]
+        is_similar = [{ $is_similar:tt }]
+    } => {
+        const $name: bool = $is_similar;
+    }
+}
+
+///
+/// We use this macro to invoke 'tt_similar' and produce a bool const of whether the
+/// two given sequences are at least as similar as the given threshold.
+///
+/// The first argument is the name of the resulting const, the second is the threshold,
+/// while the two following arguments are the sequences to be compared.
+///
+macro_rules! invoke_tt_similar {
+    {
+        $id:ident $threshold:tt $lhs:tt $rhs:tt
+    } => {
+        tt_call! {
+            macro = [{ tt_similar }]
+            input = [{ $lhs $rhs }]
+            threshold = [{ $threshold }]
+            ~~> bool_const! {
+                name = [{ $id }]
+            }
+        }
+    }
+}
+
+// 4 elements in common and 5 in the union, for a similarity of 0.8.
+invoke_tt_similar!(AT_THRESHOLD 0.8 (a, b, c, d, e) (e, d, c, b));
+invoke_tt_similar!(ABOVE_THRESHOLD 0.75 (a, b, c, d, e) (e, d, c, b));
+invoke_tt_similar!(BELOW_THRESHOLD 0.85 (a, b, c, d, e) (e, d, c, b));
+// 1 element in common (a) and 3 in the union (a, a, b), for a similarity of 1/3.
+invoke_tt_similar!(DUPLICATES_ABOVE 0.3 (a, a) (a, b));
+invoke_tt_similar!(DUPLICATES_BELOW 0.4 (a, a) (a, b));
+invoke_tt_similar!(EQUAL_SEQUENCES 1.0 (u8: 1, &str: 2) (&str: 2, u8: 1));
+invoke_tt_similar!(DISJOINT_SEQUENCES 0.1 (a, b) (c, d));
+invoke_tt_similar!(DISJOINT_ZERO_THRESHOLD 0 (a, b) (c, d));
+invoke_tt_similar!(EMPTY 1 () ());
+
+///
+/// Tests that `tt_similar` compares the Jaccard similarity of the sequences to the threshold.
+///
+#[test]
+fn test_tt_similar_invocations() {
+    assert!(AT_THRESHOLD);
+    assert!(ABOVE_THRESHOLD);
+    assert!(!BELOW_THRESHOLD);
+    assert!(DUPLICATES_ABOVE);
+    assert!(!DUPLICATES_BELOW);
+    assert!(EQUAL_SEQUENCES);
+    assert!(!DISJOINT_SEQUENCES);
+    assert!(DISJOINT_ZERO_THRESHOLD);
+    assert!(EMPTY);
+}
@@ -0,0 +1,62 @@
+//!
+//! Normalization of type ascriptions.
+//!
+use crate::bounds::{angle_bracket_depth_change, is_lone_punct};
+use proc_macro2::{TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+///
+/// Removes every type ascription in the given stream, such that e.g. `const X: i32 = 5;`
+/// becomes `const X = 5;` and `x: u8, y: u16` becomes `x, y`.
+///
+/// An ascription is a `:` (not part of `::`) and everything following it up to the next
+/// `=`, `,`, or `;` that isn't nested in angle brackets, or up to the end of the stream.
+/// A `:` following a lifetime is left as is, such that e.g. labels and the bounds of
+/// lifetimes aren't removed.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn strip_type_ascriptions(stream: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if is_lone_punct(&tokens, i, ':') && !follows_lifetime(&tokens, i) {
+            i = ascription_end(&tokens, i + 1);
+        } else {
+            result.push(tokens[i].clone());
+            i += 1;
+        }
+    }
+    TokenStream::from_iter(result)
+}
+
+///
+/// Returns the index of the `=`, `,`, or `;` ending the type starting at the given index,
+/// or the index of the `>` closing an angle bracket the type is nested in.
+///
+/// If neither is found, the length of the tokens is returned.
+///
+fn ascription_end(tokens: &[TokenTree], start: usize) -> usize {
+    let mut depth = 0;
+    for (i, tt) in tokens.iter().enumerate().skip(start) {
+        if depth == 0 && [',', ';', '='].iter().any(|c| is_lone_punct(tokens, i, *c)) {
+            return i;
+        }
+        depth += angle_bracket_depth_change(tokens, i, tt);
+        if depth < 0 {
+            return i;
+        }
+    }
+    tokens.len()
+}
+
+///
+/// Returns whether the token at the given index follows a lifetime, e.g. the `:` of `'a: 'b`.
+///
+fn follows_lifetime(tokens: &[TokenTree], i: usize) -> bool {
+    match i.checked_sub(2).map(|start| &tokens[start..i]) {
+        Some([TokenTree::Punct(apostrophe), TokenTree::Ident(_)]) => apostrophe.as_char() == '\'',
+        _ => false,
+    }
+}
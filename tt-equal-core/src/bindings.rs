@@ -0,0 +1,100 @@
+//!
+//! Normalization of the mutability of bindings.
+//!
+use crate::bounds::matching_angle_bracket;
+use crate::is_ident;
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+///
+/// Removes the `mut` of every function parameter in the given stream, such that e.g.
+/// `fn f(mut x: i32)` becomes `fn f(x: i32)`.
+///
+/// Only a `mut` that starts a parameter in the parameter list of a `fn` is removed, such that
+/// e.g. the `mut` of `&mut self` or of `x: &mut u8` is left as is.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn strip_param_mut(stream: TokenStream) -> TokenStream {
+    let mut tokens = Vec::from_iter(stream);
+    for i in 0..tokens.len() {
+        if let Some(list) = param_list(&tokens, i) {
+            let params = Vec::from_iter(list.stream());
+            let stripped = params
+                .iter()
+                .enumerate()
+                .filter(|(i, tt)| !(is_ident(tt, "mut") && starts_param(&params, *i)))
+                .map(|(_, tt)| tt.clone());
+            let mut stripped_list =
+                Group::new(Delimiter::Parenthesis, TokenStream::from_iter(stripped));
+            stripped_list.set_span(list.span());
+            tokens[i] = TokenTree::Group(stripped_list);
+        }
+    }
+    TokenStream::from_iter(tokens)
+}
+
+///
+/// Removes the `mut` of every `let` binding in the given stream, such that e.g.
+/// `let mut x = 1;` becomes `let x = 1;`.
+///
+/// Only a `mut` directly following `let` is removed, such that e.g. the `mut` of
+/// `let x = &mut y;` or `let (mut a, b) = c;` is left as is.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn strip_let_mut(stream: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    let result = tokens
+        .iter()
+        .enumerate()
+        .filter(|(i, tt)| {
+            !(is_ident(tt, "mut")
+                && i.checked_sub(1)
+                    .is_some_and(|l| is_ident(&tokens[l], "let")))
+        })
+        .map(|(_, tt)| tt.clone());
+    TokenStream::from_iter(result)
+}
+
+///
+/// If the token at the given index is the parameter list of a function, i.e. a parenthesized
+/// group following `fn`, the function's name, and any generic parameters, returns it.
+///
+fn param_list(tokens: &[TokenTree], i: usize) -> Option<&Group> {
+    let list = match &tokens[i] {
+        TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => group,
+        _ => return None,
+    };
+    let name = match i.checked_sub(1).map(|prev| &tokens[prev]) {
+        Some(TokenTree::Punct(close)) if close.as_char() == '>' => {
+            // The generic parameters, whose '<' must follow the name.
+            (0..i - 1)
+                .rev()
+                .find(|open| matching_angle_bracket(tokens, *open) == Some(i - 1))?
+                .checked_sub(1)?
+        }
+        _ => i.checked_sub(1)?,
+    };
+    let is_name = matches!(tokens[name], TokenTree::Ident(_));
+    let follows_fn = name
+        .checked_sub(1)
+        .is_some_and(|f| is_ident(&tokens[f], "fn"));
+    if is_name && follows_fn {
+        Some(list)
+    } else {
+        None
+    }
+}
+
+///
+/// Returns whether the token at the given index starts a parameter, i.e. it starts the given
+/// parameters or follows a `,`.
+///
+fn starts_param(params: &[TokenTree], i: usize) -> bool {
+    match i.checked_sub(1).map(|prev| &params[prev]) {
+        None => true,
+        Some(TokenTree::Punct(comma)) => comma.as_char() == ',',
+        Some(_) => false,
+    }
+}
@@ -0,0 +1,34 @@
+//!
+//! Normalization of blocks.
+//!
+use crate::is_ident;
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+///
+/// Replaces every `unsafe` block in the given stream with its contents, such that e.g.
+/// `unsafe { foo() }` becomes `foo()`.
+///
+/// Only an `unsafe` directly followed by a brace group is a block, such that e.g.
+/// `unsafe fn` and `unsafe impl` are left as is. Blocks directly nested in a block are
+/// replaced too, as their contents become part of the stream.
+///
+/// Other nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn unwrap_unsafe_blocks(stream: TokenStream) -> TokenStream {
+    let mut result = Vec::new();
+    let mut tokens = stream.into_iter().peekable();
+    while let Some(tt) = tokens.next() {
+        match tokens.peek() {
+            Some(TokenTree::Group(block))
+                if is_ident(&tt, "unsafe") && block.delimiter() == Delimiter::Brace =>
+            {
+                let contents = block.stream();
+                tokens.next();
+                result.extend(unwrap_unsafe_blocks(contents));
+            }
+            _ => result.push(tt),
+        }
+    }
+    TokenStream::from_iter(result)
+}
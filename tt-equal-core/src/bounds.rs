@@ -0,0 +1,272 @@
+//!
+//! Normalization of where the bounds of generic parameters are placed in function signatures.
+//!
+use crate::{is_ident, token_strings};
+use proc_macro2::{Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+///
+/// The bounds of a signature, keyed by the string representation of the bounded type.
+///
+/// Each entry holds the bounded type's tokens and its bounds, keyed by their string
+/// representations, such that duplicates are merged and the order is canonical.
+///
+type Predicates = BTreeMap<String, (Vec<TokenTree>, BTreeMap<String, Vec<TokenTree>>)>;
+
+///
+/// Moves the bounds of the generic parameters of every function signature in the given
+/// stream into the signature's where clause.
+///
+/// The where clause is made canonical too: predicates on the same type are merged and
+/// both the predicates and their bounds are sorted.
+/// E.g. both `fn f<T: Clone>()` and `fn f<T>() where T: Clone` become
+/// `fn f<T>() where T: Clone`.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn normalize_bounds_position(stream: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if is_ident(&tokens[i], "fn") && matches!(tokens.get(i + 1), Some(TokenTree::Ident(_))) {
+            i = normalize_signature(&tokens, i, &mut result);
+        } else {
+            result.push(tokens[i].clone());
+            i += 1;
+        }
+    }
+    TokenStream::from_iter(result)
+}
+
+///
+/// Normalizes the signature starting with the `fn` at the given index, pushing it to the result.
+///
+/// The signature ends before its body or terminating `;`.
+/// Returns the index of the first token after the signature.
+///
+fn normalize_signature(tokens: &[TokenTree], start: usize, result: &mut Vec<TokenTree>) -> usize {
+    result.extend_from_slice(&tokens[start..start + 2]);
+    let mut i = start + 2;
+    let mut predicates = Predicates::new();
+
+    if is_punct(&tokens[i..], '<') {
+        let end = match matching_angle_bracket(tokens, i) {
+            Some(end) => end,
+            None => {
+                // Not a generic parameter list we understand, so leave the rest as is.
+                result.extend_from_slice(&tokens[i..]);
+                return tokens.len();
+            }
+        };
+        result.push(tokens[i].clone());
+        for (n, param) in split_top_level(&tokens[i + 1..end], ',').iter().enumerate() {
+            if n > 0 {
+                result.push(punct(','));
+            }
+            if is_ident(&param[0], "const") {
+                result.extend_from_slice(param);
+                continue;
+            }
+            let (name_and_bounds, default) = match split_once_top_level(param, '=') {
+                Some((name_and_bounds, default)) => (name_and_bounds, Some(default)),
+                None => (&param[..], None),
+            };
+            match split_once_top_level(name_and_bounds, ':') {
+                Some((name, bounds)) => {
+                    add_bounds(&mut predicates, name, bounds);
+                    result.extend_from_slice(name);
+                }
+                None => result.extend_from_slice(name_and_bounds),
+            }
+            if let Some(default) = default {
+                result.push(punct('='));
+                result.extend_from_slice(default);
+            }
+        }
+        result.push(tokens[end].clone());
+        i = end + 1;
+    }
+
+    while i < tokens.len() && !ends_signature(&tokens[i]) {
+        if is_ident(&tokens[i], "where") {
+            let clause_start = i + 1;
+            i = clause_start;
+            while i < tokens.len() && !ends_signature(&tokens[i]) {
+                i += 1;
+            }
+            for predicate in split_top_level(&tokens[clause_start..i], ',') {
+                match split_once_top_level(&predicate, ':') {
+                    Some((bounded, bounds)) => add_bounds(&mut predicates, bounded, bounds),
+                    None => add_bounds(&mut predicates, &predicate, &[]),
+                }
+            }
+        } else {
+            result.push(tokens[i].clone());
+            i += 1;
+        }
+    }
+
+    if !predicates.is_empty() {
+        result.push(TokenTree::Ident(Ident::new("where", Span::call_site())));
+        for (n, (bounded, bounds)) in predicates.values().enumerate() {
+            if n > 0 {
+                result.push(punct(','));
+            }
+            result.extend_from_slice(bounded);
+            result.push(punct(':'));
+            for (k, bound) in bounds.values().enumerate() {
+                if k > 0 {
+                    result.push(punct('+'));
+                }
+                result.extend_from_slice(bound);
+            }
+        }
+    }
+    i
+}
+
+///
+/// Adds the given `+`-separated bounds to the predicate on the given bounded type.
+///
+fn add_bounds(predicates: &mut Predicates, bounded: &[TokenTree], bounds: &[TokenTree]) {
+    let (_, existing) = predicates
+        .entry(token_strings(bounded).concat())
+        .or_insert_with(|| (bounded.to_vec(), BTreeMap::new()));
+    for bound in split_top_level(bounds, '+') {
+        existing.insert(token_strings(&bound).concat(), bound);
+    }
+}
+
+///
+/// Returns whether the given token ends a function signature, i.e. is its body or a `;`.
+///
+fn ends_signature(tt: &TokenTree) -> bool {
+    match tt {
+        TokenTree::Group(group) => group.delimiter() == proc_macro2::Delimiter::Brace,
+        TokenTree::Punct(p) => p.as_char() == ';',
+        _ => false,
+    }
+}
+
+///
+/// Returns the index of the `>` closing the `<` at the given index.
+///
+pub(crate) fn matching_angle_bracket(tokens: &[TokenTree], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, tt) in tokens.iter().enumerate().skip(open) {
+        depth += angle_bracket_depth_change(tokens, i, tt);
+        if depth == 0 {
+            return Some(i);
+        }
+    }
+    None
+}
+
+///
+/// Returns how the given token, at the given index, changes the nesting depth of angle brackets.
+///
+/// The `>` of `->` and `=>` are not closing angle brackets.
+///
+pub(crate) fn angle_bracket_depth_change(tokens: &[TokenTree], i: usize, tt: &TokenTree) -> i32 {
+    match tt {
+        TokenTree::Punct(p) if p.as_char() == '<' => 1,
+        TokenTree::Punct(p) if p.as_char() == '>' => match i.checked_sub(1).map(|i| &tokens[i]) {
+            Some(TokenTree::Punct(prev))
+                if prev.spacing() == Spacing::Joint && "-=".contains(prev.as_char()) =>
+            {
+                0
+            }
+            _ => -1,
+        },
+        _ => 0,
+    }
+}
+
+///
+/// The two-character punctuations that a separator could be part of.
+///
+const JOINED_PUNCTUATIONS: &[&str] = &[
+    "::", "==", "!=", "<=", ">=", "=>", "+=", "-=", "*=", "/=", "%=", "^=", "&=", "|=",
+];
+
+///
+/// Returns whether the token at the given index is the given punctuation on its own,
+/// i.e. not part of a multi-character punctuation like `::` or `+=`.
+///
+/// A punctuation's spacing alone doesn't tell, as e.g. the `>` in `Into<u8>,` is joint
+/// with the comma following it.
+///
+pub(crate) fn is_lone_punct(tokens: &[TokenTree], i: usize, c: char) -> bool {
+    let joined = |first: Option<&TokenTree>, second: Option<&TokenTree>| match (first, second) {
+        (Some(TokenTree::Punct(first)), Some(TokenTree::Punct(second))) => {
+            first.spacing() == Spacing::Joint
+                && JOINED_PUNCTUATIONS.contains(
+                    &[first.as_char(), second.as_char()]
+                        .iter()
+                        .collect::<String>()
+                        .as_str(),
+                )
+        }
+        _ => false,
+    };
+    is_punct(&tokens[i..], c)
+        && !joined(i.checked_sub(1).map(|prev| &tokens[prev]), tokens.get(i))
+        && !joined(tokens.get(i), tokens.get(i + 1))
+}
+
+///
+/// Splits the given tokens at each of the given punctuation that isn't nested in angle brackets.
+///
+/// Empty elements (e.g. after a trailing separator) are dropped.
+///
+pub(crate) fn split_top_level(tokens: &[TokenTree], separator: char) -> Vec<Vec<TokenTree>> {
+    let mut elements = vec![Vec::new()];
+    let mut depth = 0;
+    for (i, tt) in tokens.iter().enumerate() {
+        depth += angle_bracket_depth_change(tokens, i, tt);
+        if depth == 0 && is_lone_punct(tokens, i, separator) {
+            elements.push(Vec::new());
+        } else {
+            elements.last_mut().unwrap().push(tt.clone());
+        }
+    }
+    elements.retain(|element| !element.is_empty());
+    elements
+}
+
+///
+/// Splits the given tokens at the first of the given punctuation that isn't nested in
+/// angle brackets, returning the tokens before and after it.
+///
+pub(crate) fn split_once_top_level(
+    tokens: &[TokenTree],
+    separator: char,
+) -> Option<(&[TokenTree], &[TokenTree])> {
+    let mut depth = 0;
+    for (i, tt) in tokens.iter().enumerate() {
+        depth += angle_bracket_depth_change(tokens, i, tt);
+        if depth == 0 && is_lone_punct(tokens, i, separator) {
+            return Some((&tokens[..i], &tokens[i + 1..]));
+        }
+    }
+    None
+}
+
+///
+/// Returns whether the first of the given tokens is the given punctuation.
+///
+fn is_punct(tokens: &[TokenTree], c: char) -> bool {
+    match tokens.first() {
+        Some(TokenTree::Punct(p)) => p.as_char() == c,
+        _ => false,
+    }
+}
+
+///
+/// Returns the given punctuation as an alone token.
+///
+pub(crate) fn punct(c: char) -> TokenTree {
+    TokenTree::Punct(Punct::new(c, Spacing::Alone))
+}
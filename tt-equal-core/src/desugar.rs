@@ -0,0 +1,103 @@
+//!
+//! Normalization of desugared expressions into their sugared forms.
+//!
+use crate::{is_ident, tokens_equal};
+use proc_macro2::{Delimiter, Punct, Spacing, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+///
+/// Replaces every `match` expression in the given stream that is the canonical desugaring
+/// of the `?` operator with the equivalent `?` expression, such that e.g.
+/// `match foo() { Ok(v) => v, Err(e) => return Err(e.into()) }` becomes `foo()?`.
+///
+/// Only the canonical desugaring is replaced: the arms must be exactly as above, in that
+/// order, though the names of the bindings may differ and a trailing comma is allowed.
+/// E.g. `Err(e) => return Err(From::from(e))` isn't replaced, even though it's equivalent.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn sugar_try(stream: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if is_ident(&tokens[i], "match") {
+            if let Some(arms) = match_arms(&tokens, i) {
+                if is_try_arms(&tokens[arms]) {
+                    result.extend_from_slice(&tokens[i + 1..arms]);
+                    // Rust makes a punctuation joint with any punctuation following it.
+                    let spacing = match tokens.get(arms + 1) {
+                        Some(TokenTree::Punct(_)) => Spacing::Joint,
+                        _ => Spacing::Alone,
+                    };
+                    result.push(TokenTree::Punct(Punct::new('?', spacing)));
+                    i = arms + 1;
+                    continue;
+                }
+            }
+        }
+        result.push(tokens[i].clone());
+        i += 1;
+    }
+    TokenStream::from_iter(result)
+}
+
+///
+/// Returns the index of the brace group holding the arms of the `match` at the given index.
+///
+/// A scrutinee may contain brace groups itself, e.g. `match unsafe { f() } { .. }`, so the arms
+/// are the last brace group before the end of the `match`, which is taken to be the first `;`,
+/// `,`, or following `match` at the same level, or the end of the stream.
+/// If there is no brace group after a non-empty scrutinee, `None` is returned.
+///
+fn match_arms(tokens: &[TokenTree], start: usize) -> Option<usize> {
+    let end = tokens[start + 1..]
+        .iter()
+        .position(|tt| match tt {
+            TokenTree::Punct(p) => p.as_char() == ';' || p.as_char() == ',',
+            _ => is_ident(tt, "match"),
+        })
+        .map_or(tokens.len(), |end| start + 1 + end);
+    tokens
+        .get(start + 2..end)?
+        .iter()
+        .rposition(
+            |tt| matches!(tt, TokenTree::Group(group) if group.delimiter() == Delimiter::Brace),
+        )
+        .map(|arms| start + 2 + arms)
+}
+
+///
+/// Returns whether the given token is the arms of the canonical desugaring of `?`,
+/// i.e. `{ Ok(v) => v, Err(e) => return Err(e.into()) }` with any names for the bindings.
+///
+fn is_try_arms(arms: &TokenTree) -> bool {
+    let arms = match arms {
+        TokenTree::Group(group) => Vec::from_iter(group.stream()),
+        _ => return false,
+    };
+    let binding = |tt: Option<&TokenTree>| match tt {
+        Some(TokenTree::Group(group)) => match Vec::from_iter(group.stream()).as_slice() {
+            [TokenTree::Ident(name)] => Some(name.to_string()),
+            _ => None,
+        },
+        _ => None,
+    };
+    match (binding(arms.get(1)), binding(arms.get(7))) {
+        (Some(ok), Some(err)) => {
+            let canonical: TokenStream = format!(
+                "Ok({ok}) => {ok}, Err({err}) => return Err({err}.into())",
+                ok = ok,
+                err = err
+            )
+            .parse()
+            .unwrap();
+            let arms = match arms.split_last() {
+                Some((TokenTree::Punct(comma), arms)) if comma.as_char() == ',' => arms,
+                _ => &arms[..],
+            };
+            tokens_equal(&TokenStream::from_iter(arms.to_vec()), &canonical)
+        }
+        _ => false,
+    }
+}
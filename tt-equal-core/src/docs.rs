@@ -0,0 +1,193 @@
+//!
+//! Normalization of consecutive doc attributes into a single one.
+//!
+use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use std::convert::TryFrom;
+use std::iter::FromIterator;
+
+///
+/// Concatenates the values of every run of consecutive `#[doc = "..."]` attributes in the
+/// given stream, separated by newlines, into a single doc attribute.
+///
+/// Since `///` doc comments are turned into doc attributes, e.g. both
+/// ```text
+/// /// First line
+/// /// Second line
+/// ```
+/// and `#[doc = " First line\n Second line"]` become the latter.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn concat_doc_attrs(stream: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let mut lines = Vec::new();
+        while let Some(line) = doc_attr_value(&tokens[i..]) {
+            lines.push(line);
+            i += 2;
+        }
+        if lines.is_empty() {
+            result.push(tokens[i].clone());
+            i += 1;
+        } else {
+            result.extend(doc_attr(&lines.join("\n")));
+        }
+    }
+    TokenStream::from_iter(result)
+}
+
+///
+/// If the given tokens start with a `#[doc = "..."]` attribute, returns the attribute's value.
+///
+fn doc_attr_value(tokens: &[TokenTree]) -> Option<String> {
+    match tokens {
+        [TokenTree::Punct(hash), TokenTree::Group(body), ..]
+            if hash.as_char() == '#' && body.delimiter() == Delimiter::Bracket =>
+        {
+            match Vec::from_iter(body.stream()).as_slice() {
+                [TokenTree::Ident(doc), TokenTree::Punct(eq), TokenTree::Literal(value)]
+                    if doc == "doc" && eq.as_char() == '=' =>
+                {
+                    string_literal_value(&value.to_string())
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+///
+/// Returns the `#[doc = "..."]` attribute with the given value.
+///
+fn doc_attr(value: &str) -> Vec<TokenTree> {
+    let body = TokenStream::from_iter(vec![
+        TokenTree::Ident(Ident::new("doc", Span::call_site())),
+        TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+        TokenTree::Literal(Literal::string(value)),
+    ]);
+    vec![
+        TokenTree::Punct(Punct::new('#', Spacing::Alone)),
+        TokenTree::Group(Group::new(Delimiter::Bracket, body)),
+    ]
+}
+
+///
+/// Returns the value of the given string literal, with any escapes resolved.
+///
+/// If the literal isn't a string literal (e.g. it is a byte string), `None` is returned.
+///
+pub(crate) fn string_literal_value(literal: &str) -> Option<String> {
+    if let Some(raw) = literal.strip_prefix('r') {
+        return raw_contents(raw).map(str::to_string);
+    }
+    unescape(literal.strip_prefix('"')?.strip_suffix('"')?)
+}
+
+///
+/// Returns the contents of the given raw literal without its `r` prefix, e.g. `abc` for
+/// `#"abc"#`.
+///
+fn raw_contents(raw: &str) -> Option<&str> {
+    let hashes = raw.len() - raw.trim_start_matches('#').len();
+    raw.get(hashes + 1..raw.len() - hashes - 1)
+}
+
+///
+/// Returns the value of the given character literal, with any escape resolved.
+///
+/// If the literal isn't a character literal (e.g. it is a byte), `None` is returned.
+///
+pub(crate) fn char_literal_value(literal: &str) -> Option<char> {
+    let value = unescape(literal.strip_prefix('\'')?.strip_suffix('\'')?)?;
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+///
+/// Returns the value of the given byte literal, e.g. `b'a'`, with any escape resolved.
+///
+/// If the literal isn't a byte literal (e.g. it is a character), `None` is returned.
+///
+pub(crate) fn byte_literal_value(literal: &str) -> Option<u8> {
+    let value = unescape(literal.strip_prefix("b'")?.strip_suffix('\'')?)?;
+    match bytes(&value)?.as_slice() {
+        [byte] => Some(*byte),
+        _ => None,
+    }
+}
+
+///
+/// Returns the value of the given byte string literal, e.g. `b"abc"`, with any escapes resolved.
+///
+/// Raw byte strings, e.g. `br"abc"` and `br#"abc"#`, are supported too.
+/// If the literal isn't a byte string literal (e.g. it is a string), `None` is returned.
+///
+pub(crate) fn byte_string_literal_value(literal: &str) -> Option<Vec<u8>> {
+    if let Some(raw) = literal.strip_prefix("br") {
+        return raw_contents(raw).map(|contents| contents.as_bytes().to_vec());
+    }
+    bytes(&unescape(literal.strip_prefix("b\"")?.strip_suffix('"')?)?)
+}
+
+///
+/// Returns the bytes of the given decoded byte or byte string literal, in which each character
+/// stands for the byte of its code point, as e.g. the escape `\xFF` is decoded to `ÿ`.
+///
+fn bytes(value: &str) -> Option<Vec<u8>> {
+    value
+        .chars()
+        .map(|c| u8::try_from(u32::from(c)).ok())
+        .collect()
+}
+
+///
+/// Returns the given contents of a non-raw literal with its escapes resolved.
+///
+/// If an escape is malformed, `None` is returned.
+///
+fn unescape(escaped: &str) -> Option<String> {
+    let mut value = String::new();
+    let mut chars = escaped.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => value.push('\n'),
+            'r' => value.push('\r'),
+            't' => value.push('\t'),
+            '0' => value.push('\0'),
+            'x' => {
+                let hex = String::from_iter(chars.by_ref().take(2));
+                value.push(char::from(u8::from_str_radix(&hex, 16).ok()?));
+            }
+            'u' => {
+                // The hex digits may be separated by underscores, e.g. `\u{1_F600}`.
+                let hex = String::from_iter(
+                    chars
+                        .by_ref()
+                        .take_while(|c| *c != '}')
+                        .filter(|c| *c != '_'),
+                );
+                value.push(char::from_u32(
+                    u32::from_str_radix(hex.trim_start_matches('{'), 16).ok()?,
+                )?);
+            }
+            '\n' => {
+                // A line continuation skips the newline and any following whitespace.
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+            }
+            escaped => value.push(escaped),
+        }
+    }
+    Some(value)
+}
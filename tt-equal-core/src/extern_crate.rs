@@ -0,0 +1,35 @@
+//!
+//! Normalization of `extern crate` items.
+//!
+use crate::is_ident;
+use proc_macro2::{TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+///
+/// Removes the rename of every `extern crate` item in the given stream, such that e.g.
+/// `extern crate foo as bar;` becomes `extern crate foo;`.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn strip_extern_crate_rename(stream: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i..] {
+            [TokenTree::Ident(_), TokenTree::Ident(_), TokenTree::Ident(_), TokenTree::Ident(_), TokenTree::Ident(_), ..]
+                if is_ident(&tokens[i], "extern")
+                    && is_ident(&tokens[i + 1], "crate")
+                    && is_ident(&tokens[i + 3], "as") =>
+            {
+                result.extend_from_slice(&tokens[i..i + 3]);
+                i += 5;
+            }
+            _ => {
+                result.push(tokens[i].clone());
+                i += 1;
+            }
+        }
+    }
+    TokenStream::from_iter(result)
+}
@@ -0,0 +1,123 @@
+//!
+//! Normalization of generic arguments.
+//!
+use crate::bounds::{
+    is_lone_punct, matching_angle_bracket, punct, split_once_top_level, split_top_level,
+};
+use crate::{is_ident, token_strings};
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+///
+/// Sorts the associated type bindings of every generic argument list in the given stream by
+/// their names, such that e.g. both `Iterator<Item = u8, IntoIter = I>` and
+/// `Iterator<IntoIter = I, Item = u8>` become `Iterator<IntoIter = I, Item = u8>`.
+///
+/// Positional arguments keep their order and are placed before the bindings, as Rust
+/// requires. Argument lists nested in the arguments are sorted too, while argument lists
+/// without bindings are left as is.
+///
+/// Any `<` with a matching `>` is taken to start an argument list, so e.g. the comparisons in
+/// `a < b && c > d` are too, but without bindings, they are left as is.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn sort_assoc_bindings(stream: TokenStream) -> TokenStream {
+    TokenStream::from_iter(sort_in(&Vec::from_iter(stream)))
+}
+
+///
+/// Sorts the associated type bindings of every generic argument list in the given tokens.
+///
+fn sort_in(tokens: &[TokenTree]) -> Vec<TokenTree> {
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let end = if is_lone_punct(tokens, i, '<') {
+            matching_angle_bracket(tokens, i)
+        } else {
+            None
+        };
+        let args = end.map(|end| split_top_level(&tokens[i + 1..end], ','));
+        match (end, args) {
+            (Some(end), Some(args)) if args.iter().any(|arg| is_binding(arg)) => {
+                let (mut bindings, positional): (Vec<_>, Vec<_>) =
+                    args.into_iter().partition(|arg| is_binding(arg));
+                bindings.sort_by_key(|binding| token_strings(&binding[..1]));
+                result.push(tokens[i].clone());
+                for (n, arg) in positional.iter().chain(&bindings).enumerate() {
+                    if n > 0 {
+                        result.push(punct(','));
+                    }
+                    result.extend(sort_in(arg));
+                }
+                result.push(tokens[end].clone());
+                i = end + 1;
+            }
+            _ => {
+                result.push(tokens[i].clone());
+                i += 1;
+            }
+        }
+    }
+    result
+}
+
+///
+/// Returns whether the given generic argument is an associated type binding, i.e. `Name = Type`.
+///
+fn is_binding(arg: &[TokenTree]) -> bool {
+    matches!(arg.first(), Some(TokenTree::Ident(_))) && arg.len() > 2 && is_lone_punct(arg, 1, '=')
+}
+
+///
+/// Replaces the error type of every `Result<T, E>` in the given stream with `_`, such that e.g.
+/// both `Result<u8, X>` and `Result<u8, Y>` become `Result<u8, _>`.
+///
+/// Only the `Result` identifier followed by exactly two generic arguments is affected, such
+/// that e.g. the single argument of an `io::Result<u8>` alias is left as is, as are results
+/// under other names. Results nested in the success type are replaced too.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn replace_result_errs(stream: TokenStream) -> TokenStream {
+    TokenStream::from_iter(replace_in(&Vec::from_iter(stream)))
+}
+
+///
+/// Replaces the error type of every `Result<T, E>` in the given tokens with `_`.
+///
+fn replace_in(tokens: &[TokenTree]) -> Vec<TokenTree> {
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let end = if is_ident(&tokens[i], "Result")
+            && tokens.len() > i + 1
+            && is_lone_punct(tokens, i + 1, '<')
+        {
+            matching_angle_bracket(tokens, i + 1)
+        } else {
+            None
+        };
+        let args = end.and_then(|end| split_once_top_level(&tokens[i + 2..end], ','));
+        match (end, args) {
+            (Some(end), Some((ok, err)))
+                if !ok.is_empty()
+                    && !err.is_empty()
+                    && split_once_top_level(err, ',').is_none() =>
+            {
+                result.extend(tokens[i..i + 2].iter().cloned());
+                result.extend(replace_in(ok));
+                result.push(punct(','));
+                result.push(TokenTree::Ident(Ident::new("_", tokens[i].span())));
+                result.push(tokens[end].clone());
+                i = end + 1;
+            }
+            _ => {
+                result.push(tokens[i].clone());
+                i += 1;
+            }
+        }
+    }
+    result
+}
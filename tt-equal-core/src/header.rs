@@ -0,0 +1,59 @@
+//!
+//! Truncation of declarations to their headers.
+//!
+use crate::bounds::{angle_bracket_depth_change, is_lone_punct};
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+///
+/// Truncates the given side of a comparison to the header of the declaration it holds
+/// (see `truncate_to_header`).
+///
+/// As a declaration is usually given wrapped in a group, e.g. `{fn f() {}}`, a side that is
+/// a single group has its contents truncated instead. Any other groups are left as is, such
+/// that e.g. the `;` of the array types in `fn f(x: [u8; 4]) -> [u8; 4]` doesn't truncate
+/// them, and the arrays are still compared.
+///
+pub(crate) fn truncate_side_to_header(stream: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    match tokens.as_slice() {
+        [TokenTree::Group(group)] => {
+            let contents = if group.delimiter() == Delimiter::None {
+                truncate_side_to_header(group.stream())
+            } else {
+                truncate_to_header(group.stream())
+            };
+            let mut truncated = Group::new(group.delimiter(), contents);
+            truncated.set_span(group.span());
+            TokenStream::from(TokenTree::Group(truncated))
+        }
+        _ => truncate_to_header(TokenStream::from_iter(tokens)),
+    }
+}
+
+///
+/// Truncates the given stream at the first body-starting token, i.e. a brace group, `;`,
+/// or `=`, such that e.g. both `fn f() -> u8 { 1 }` and `fn f() -> u8 { 2 }` become
+/// `fn f() -> u8`.
+///
+/// Tokens nested in angle brackets don't start a body (e.g. the `=` of
+/// `struct S<T = u8>`), nor does the first token of the stream, such that a side that
+/// is a single group isn't truncated to nothing.
+///
+/// Nested groups are left as is.
+///
+fn truncate_to_header(stream: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    let mut depth = 0;
+    for (i, tt) in tokens.iter().enumerate() {
+        depth += angle_bracket_depth_change(&tokens, i, tt);
+        let starts_body = match tt {
+            TokenTree::Group(group) => group.delimiter() == Delimiter::Brace,
+            _ => is_lone_punct(&tokens, i, ';') || is_lone_punct(&tokens, i, '='),
+        };
+        if i > 0 && depth == 0 && starts_body {
+            return TokenStream::from_iter(tokens.into_iter().take(i));
+        }
+    }
+    TokenStream::from_iter(tokens)
+}
@@ -0,0 +1,255 @@
+//!
+//! Normalization of impl blocks.
+//!
+use crate::bounds::{angle_bracket_depth_change, is_lone_punct, matching_angle_bracket};
+use crate::is_ident;
+use proc_macro2::{Delimiter, Group, Ident, Spacing, TokenStream, TokenTree};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+///
+/// Renames the generic parameters of every impl block in the given stream by their position,
+/// such that e.g. both `impl<T> Foo for Bar<T> {}` and `impl<U> Foo for Bar<U> {}` become
+/// the same impl block.
+///
+/// The parameters are renamed everywhere in the impl block, including its body and any
+/// groups nested in it. Lifetime and const parameters are renamed too, where a lifetime
+/// parameter only renames lifetimes and the other parameters only rename identifiers that
+/// aren't lifetimes, such that e.g. the function `a` of `impl<'a> S<'a> { fn a() {} }` keeps
+/// its name.
+/// Paths like `Self::T` and items redeclaring a parameter, like `fn a<T>()`, are left as is
+/// (see `rename`).
+///
+pub(crate) fn rename_impl_generics(stream: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        match impl_body(&tokens, i) {
+            Some(body) if is_lone_punct(&tokens, i + 1, '<') => {
+                let end = matching_angle_bracket(&tokens, i + 1).unwrap();
+                let mut lifetimes = BTreeMap::new();
+                let mut others = BTreeMap::new();
+                let params = generic_param_names(&tokens[i + 2..end]);
+                for (n, (name, is_lifetime)) in params.into_iter().enumerate() {
+                    let names = if is_lifetime {
+                        &mut lifetimes
+                    } else {
+                        &mut others
+                    };
+                    names.insert(name, format!("__impl_generic_{}", n));
+                }
+                result.extend(rename(&tokens[i..=body], &lifetimes, &others));
+                i = body + 1;
+            }
+            _ => {
+                result.push(tokens[i].clone());
+                i += 1;
+            }
+        }
+    }
+    TokenStream::from_iter(result)
+}
+
+///
+/// Reduces every impl block in the given stream to its subject, i.e. `impl Trait for Type`
+/// for trait impls and `impl Type` for inherent impls, such that e.g. both
+/// `impl<T: Clone> Foo for Bar<T> { fn a() {} }` and `impl<T> Foo for Bar<T> where T: Copy {}`
+/// become `impl Foo for Bar<T>`.
+///
+/// The generic parameters, where clause, and body of the impl block are dropped.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn truncate_to_impl_subject(stream: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        match impl_body(&tokens, i) {
+            Some(body) => {
+                let mut start = i + 1;
+                if is_lone_punct(&tokens, start, '<') {
+                    start = matching_angle_bracket(&tokens, start).unwrap() + 1;
+                }
+                let mut end = start;
+                let mut depth = 0;
+                while end < body {
+                    depth += angle_bracket_depth_change(&tokens, end, &tokens[end]);
+                    if depth == 0 && is_ident(&tokens[end], "where") {
+                        break;
+                    }
+                    end += 1;
+                }
+                result.push(tokens[i].clone());
+                result.extend_from_slice(&tokens[start..end]);
+                i = body + 1;
+            }
+            None => {
+                result.push(tokens[i].clone());
+                i += 1;
+            }
+        }
+    }
+    TokenStream::from_iter(result)
+}
+
+///
+/// If the token at the given index is the `impl` starting an impl block, returns the index of
+/// the block's body.
+///
+/// An `impl` only starts an impl block if it starts an item, i.e. it is the first token or
+/// follows a `;`, a group (e.g. an attribute or a preceding item's body), `unsafe`, or
+/// `default`, such that e.g. the `impl` of `fn f() -> impl Foo {}` doesn't.
+///
+fn impl_body(tokens: &[TokenTree], i: usize) -> Option<usize> {
+    if !is_ident(&tokens[i], "impl") {
+        return None;
+    }
+    let starts_item = match i.checked_sub(1).map(|prev| &tokens[prev]) {
+        None | Some(TokenTree::Group(_)) => true,
+        Some(TokenTree::Punct(p)) => p.as_char() == ';',
+        Some(prev) => is_ident(prev, "unsafe") || is_ident(prev, "default"),
+    };
+    if !starts_item {
+        return None;
+    }
+    let mut start = i + 1;
+    if is_lone_punct(tokens, start, '<') {
+        start = matching_angle_bracket(tokens, start)? + 1;
+    }
+    let mut depth = 0;
+    for (j, tt) in tokens.iter().enumerate().skip(start) {
+        depth += angle_bracket_depth_change(tokens, j, tt);
+        match tt {
+            TokenTree::Group(group) if depth == 0 && group.delimiter() == Delimiter::Brace => {
+                return Some(j)
+            }
+            TokenTree::Punct(p) if p.as_char() == ';' => return None,
+            _ => (),
+        }
+    }
+    None
+}
+
+///
+/// Returns the names of the given generic parameters and whether each is a lifetime, e.g.
+/// `a` (a lifetime), `T`, and `N` for `'a, T: Clone, const N: usize`.
+///
+fn generic_param_names(params: &[TokenTree]) -> Vec<(String, bool)> {
+    let mut names = Vec::new();
+    let mut depth = 0;
+    let mut expect_name = true;
+    for (i, tt) in params.iter().enumerate() {
+        depth += angle_bracket_depth_change(params, i, tt);
+        match tt {
+            TokenTree::Ident(ident) if expect_name && ident != "const" => {
+                let is_lifetime = i > 0 && is_lone_punct(params, i - 1, '\'');
+                names.push((ident.to_string(), is_lifetime));
+                expect_name = false;
+            }
+            _ if depth == 0 && is_lone_punct(params, i, ',') => expect_name = true,
+            _ => (),
+        }
+    }
+    names
+}
+
+///
+/// Renames the identifiers in the given tokens, including those nested in groups, as given
+/// by the maps from old to new names: the identifiers of lifetimes, i.e. those following a
+/// `'`, by the map of lifetimes, and any other identifiers by the map of other names.
+///
+/// Identifiers following a `::` aren't renamed, as they name e.g. associated items like the
+/// `T` of `Self::T`. Neither are the generic parameters of functions and type aliases that
+/// redeclare a name, within those items, such that e.g. the `T` of `fn a<T>(t: T) {}` is kept.
+///
+fn rename(
+    tokens: &[TokenTree],
+    lifetimes: &BTreeMap<String, String>,
+    others: &BTreeMap<String, String>,
+) -> Vec<TokenTree> {
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if let Some((end, shadowed)) = generic_item(tokens, i) {
+            let unshadowed = |names: &BTreeMap<String, String>, is_lifetime: bool| {
+                let mut names = names.clone();
+                for (name, _) in shadowed.iter().filter(|(_, l)| *l == is_lifetime) {
+                    names.remove(name);
+                }
+                names
+            };
+            result.push(tokens[i].clone());
+            result.extend(rename(
+                &tokens[i + 1..=end],
+                &unshadowed(lifetimes, true),
+                &unshadowed(others, false),
+            ));
+            i = end + 1;
+            continue;
+        }
+        result.push(match &tokens[i] {
+            TokenTree::Ident(ident) if !follows_path_separator(tokens, i) => {
+                let names = if i > 0 && is_lone_punct(tokens, i - 1, '\'') {
+                    lifetimes
+                } else {
+                    others
+                };
+                match names.get(&ident.to_string()) {
+                    Some(name) => TokenTree::Ident(Ident::new(name, ident.span())),
+                    None => tokens[i].clone(),
+                }
+            }
+            TokenTree::Group(group) => {
+                let stream = Vec::from_iter(group.stream());
+                let mut renamed = Group::new(
+                    group.delimiter(),
+                    TokenStream::from_iter(rename(&stream, lifetimes, others)),
+                );
+                renamed.set_span(group.span());
+                TokenTree::Group(renamed)
+            }
+            tt => tt.clone(),
+        });
+        i += 1;
+    }
+    result
+}
+
+///
+/// If the token at the given index is the `fn` or `type` starting an item with generic
+/// parameters, returns the index of the item's body or terminating `;`, or of its last token
+/// if it has neither, along with the names of its generic parameters
+/// (see `generic_param_names`).
+///
+fn generic_item(tokens: &[TokenTree], i: usize) -> Option<(usize, Vec<(String, bool)>)> {
+    if !(is_ident(&tokens[i], "fn") || is_ident(&tokens[i], "type"))
+        || !matches!(tokens.get(i + 1), Some(TokenTree::Ident(_)))
+        || !(i + 2 < tokens.len() && is_lone_punct(tokens, i + 2, '<'))
+    {
+        return None;
+    }
+    let close = matching_angle_bracket(tokens, i + 2)?;
+    let end = tokens[close..]
+        .iter()
+        .position(|tt| match tt {
+            TokenTree::Group(group) => group.delimiter() == Delimiter::Brace,
+            TokenTree::Punct(p) => p.as_char() == ';',
+            _ => false,
+        })
+        .map_or(tokens.len() - 1, |end| close + end);
+    Some((end, generic_param_names(&tokens[i + 3..close])))
+}
+
+///
+/// Returns whether the token at the given index follows a `::`.
+///
+fn follows_path_separator(tokens: &[TokenTree], i: usize) -> bool {
+    match i.checked_sub(2).map(|start| &tokens[start..i]) {
+        Some([TokenTree::Punct(first), TokenTree::Punct(second)]) => {
+            first.as_char() == ':' && first.spacing() == Spacing::Joint && second.as_char() == ':'
+        }
+        _ => false,
+    }
+}
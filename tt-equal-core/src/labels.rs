@@ -0,0 +1,55 @@
+//!
+//! Normalization of loop and block labels.
+//!
+use crate::is_ident;
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+///
+/// Renames every label in the given stream to the same name, such that e.g. both
+/// `'outer: loop { break 'outer; }` and `'a: loop { break 'a; }` become the same loop.
+///
+/// A label is a lifetime followed by `:` and a loop or block, or a lifetime following `break`
+/// or `continue`, such that e.g. the lifetimes of `<'a: 'b>` or `&'a str` are left as is.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn rename_labels(stream: TokenStream) -> TokenStream {
+    let mut tokens = Vec::from_iter(stream);
+    for i in 0..tokens.len() {
+        if is_label(&tokens, i) {
+            if let TokenTree::Ident(name) = &tokens[i + 1] {
+                tokens[i + 1] = TokenTree::Ident(Ident::new("label", name.span()));
+            }
+        }
+    }
+    TokenStream::from_iter(tokens)
+}
+
+///
+/// Returns whether the token at the given index is the apostrophe of a label.
+///
+fn is_label(tokens: &[TokenTree], i: usize) -> bool {
+    match &tokens[i..] {
+        [TokenTree::Punct(apostrophe), TokenTree::Ident(_), rest @ ..]
+            if apostrophe.as_char() == '\'' =>
+        {
+            let defines_label = match rest {
+                [TokenTree::Punct(colon), TokenTree::Group(_), ..] => colon.as_char() == ':',
+                [TokenTree::Punct(colon), keyword, ..] => {
+                    colon.as_char() == ':'
+                        && ["loop", "while", "for"]
+                            .iter()
+                            .any(|name| is_ident(keyword, name))
+                }
+                _ => false,
+            };
+            let uses_label = i
+                .checked_sub(1)
+                .map(|prev| is_ident(&tokens[prev], "break") || is_ident(&tokens[prev], "continue"))
+                .unwrap_or(false);
+            defines_label || uses_label
+        }
+        _ => false,
+    }
+}
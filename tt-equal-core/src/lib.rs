@@ -0,0 +1,928 @@
+//!
+//! The token tree comparison behind [tt-equal](https://docs.rs/tt-equal).
+//!
+//! `tt-equal` is a procedural macro crate, so it can only export macros.
+//! This crate exposes the comparison itself, operating on `proc_macro2` types,
+//! such that other procedural macros can compare tokens exactly like `tt_equal` does
+//! without going through the tt-call protocol.
+//!
+mod ascription;
+mod bindings;
+mod blocks;
+mod bounds;
+mod desugar;
+mod docs;
+mod extern_crate;
+mod generics;
+mod header;
+mod impls;
+mod labels;
+mod lifetimes;
+mod numbers;
+mod paths;
+mod qualifiers;
+mod stringify;
+mod structs;
+mod uses;
+mod variants;
+
+use proc_macro2::{Delimiter, Ident, Literal, Punct, Spacing, TokenStream, TokenTree};
+use std::iter::Peekable;
+
+///
+/// Returns whether the two given token streams are equal.
+///
+/// Groups are equal if they have the same delimiter and their contents are equal, so whitespace
+/// inside groups is irrelevant, while e.g. `(a)` and `[a]` are not equal.
+/// Multi-character punctuation is compared as a unit, so e.g. `+=` and `+ =` are not equal.
+///
+/// # Example
+///
+/// ```
+/// use tt_equal_core::tokens_equal;
+/// use proc_macro2::TokenStream;
+///
+/// let lhs: TokenStream = "(a, b)".parse().unwrap();
+/// let rhs: TokenStream = "( a,b )".parse().unwrap();
+/// let other: TokenStream = "[a, b]".parse().unwrap();
+///
+/// assert!(tokens_equal(&lhs, &rhs));
+/// assert!(!tokens_equal(&lhs, &other));
+/// ```
+///
+pub fn tokens_equal(lhs: &TokenStream, rhs: &TokenStream) -> bool {
+    tokens_equal_in_mode(lhs, rhs, &Mode::default())
+}
+
+///
+/// Returns whether the two given token streams are equal when compared in the given mode.
+///
+/// See [`tokens_equal`](fn.tokens_equal.html) for how tokens are compared
+/// and [`Mode`](struct.Mode.html) for how each mode changes it.
+///
+pub fn tokens_equal_in_mode(lhs: &TokenStream, rhs: &TokenStream, mode: &Mode) -> bool {
+    compare(lhs, rhs, mode, &DefaultComparator)
+}
+
+///
+/// Returns whether the two given token streams are equal when identifiers, literals, and
+/// punctuation are compared by the given comparator.
+///
+/// Groups and multi-character punctuation are compared as in
+/// [`tokens_equal`](fn.tokens_equal.html), with the comparator deciding whether the tokens
+/// they consist of are equal.
+///
+/// # Example
+///
+/// ```
+/// use tt_equal_core::{tokens_equal_with, TokenComparator};
+/// use proc_macro2::{Ident, TokenStream};
+///
+/// struct IgnoreUnderscores;
+///
+/// impl TokenComparator for IgnoreUnderscores {
+///     fn compare_ident(&self, lhs: &Ident, rhs: &Ident) -> bool {
+///         lhs.to_string().replace('_', "") == rhs.to_string().replace('_', "")
+///     }
+/// }
+///
+/// let lhs: TokenStream = "(foo_bar, 1)".parse().unwrap();
+/// let rhs: TokenStream = "(foobar, 1)".parse().unwrap();
+///
+/// assert!(tokens_equal_with(&lhs, &rhs, &IgnoreUnderscores));
+/// ```
+///
+pub fn tokens_equal_with(
+    lhs: &TokenStream,
+    rhs: &TokenStream,
+    comparator: &impl TokenComparator,
+) -> bool {
+    compare(lhs, rhs, &Mode::default(), comparator)
+}
+
+///
+/// Returns whether the two given token streams are equal when compared in the given mode,
+/// with the given comparator deciding whether tokens are equal where the mode doesn't.
+///
+fn compare(
+    lhs: &TokenStream,
+    rhs: &TokenStream,
+    mode: &Mode,
+    comparator: &dyn TokenComparator,
+) -> bool {
+    if mode.exact {
+        let exact = Mode {
+            exact: true,
+            strict: true,
+            ..Mode::default()
+        };
+        return streams_equal(lhs.clone(), rhs.clone(), &exact, comparator);
+    }
+    if mode.header_only {
+        // Only the compared declarations are truncated, not e.g. the array types in their
+        // signatures, so this is done once instead of when normalizing each group.
+        return streams_equal(
+            header::truncate_side_to_header(lhs.clone()),
+            header::truncate_side_to_header(rhs.clone()),
+            mode,
+            comparator,
+        );
+    }
+    streams_equal(lhs.clone(), rhs.clone(), mode, comparator)
+}
+
+///
+/// The modes a comparison can be done in.
+///
+/// By default, all modes are off.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Mode {
+    /// Whether integer, float, and string literals are compared by their value (and the type
+    /// suffix of numbers) instead of their spelling.
+    pub semantic: bool,
+    /// Whether a glob import `::*` matches any brace group following `::`.
+    pub glob_matches_explicit: bool,
+    /// Whether the bounds of a function's generic parameters are compared regardless of
+    /// whether they are given inline or in the where clause.
+    pub normalize_bounds_position: bool,
+    /// Whether consecutive `#[doc = "..."]` attributes are compared as a single attribute
+    /// with their values concatenated.
+    pub concat_doc_attrs: bool,
+    /// Whether identifiers are compared ignoring ASCII case.
+    pub case_insensitive: bool,
+    /// Whether `const fn` and `fn` are compared as equal.
+    pub ignore_const_fn: bool,
+    /// Whether `extern crate` items are compared by the crate name only, ignoring any rename.
+    pub normalize_extern_crate: bool,
+    /// Whether string literals are compared by re-parsing their values as token streams.
+    pub normalize_stringify: bool,
+    /// Whether the fields of struct and tuple enum variants are compared by their types'
+    /// positions, ignoring the names of struct variants' fields.
+    pub variant_fields_positional: bool,
+    /// Whether groups are compared by their contents only, regardless of their delimiters.
+    pub ignore_delimiters: bool,
+    /// Whether only the headers of declarations are compared, ignoring their bodies.
+    pub header_only: bool,
+    /// Whether token trees must also be of the same kind (e.g. both identifiers) to be equal,
+    /// instead of only having equal string representations.
+    ///
+    /// Undelimited groups of a single token tree are then also not unwrapped before comparing.
+    pub strict: bool,
+    /// Whether the canonical `match` desugaring of the `?` operator is compared as the `?`
+    /// expression it desugars.
+    pub desugar_try: bool,
+    /// Whether the semantic mode compares integer literals regardless of the width of their
+    /// type suffix, e.g. `255u8` and `255u16`. Has no effect without the semantic mode.
+    pub width_independent: bool,
+    /// Whether the paths of attributes are compared regardless of whether they start with `::`.
+    pub unrooted_attr_paths: bool,
+    /// Whether token trees are compared exactly, including the spacing of punctuation, as in
+    /// the strict mode. All other modes are then ignored.
+    pub exact: bool,
+    /// Whether the semantic mode compares string literals regardless of their line endings,
+    /// e.g. `"a\r\nb"` and `"a\nb"`. Has no effect without the semantic mode.
+    pub normalize_line_endings: bool,
+    /// Whether the type suffixes of numeric literals are ignored, e.g. `1u8` and `1`.
+    pub ignore_suffixes: bool,
+    /// Whether the associated type bindings of generic arguments are compared regardless of
+    /// their order, e.g. `Iterator<Item = u8, IntoIter = I>` and
+    /// `Iterator<IntoIter = I, Item = u8>`.
+    pub assoc_bindings_unordered: bool,
+    /// Whether impl blocks are compared by their subject only, i.e. the implemented trait and
+    /// the type it is implemented for, ignoring their generic parameters, where clauses,
+    /// and bodies.
+    pub compare_impl_subject_only: bool,
+    /// Whether the generic parameters of impl blocks are compared regardless of their names,
+    /// e.g. `impl<T> Foo for Bar<T> {}` and `impl<U> Foo for Bar<U> {}`.
+    pub ignore_impl_generics_names: bool,
+    /// Whether the labels of loops and blocks are compared regardless of their names, e.g.
+    /// `'a: loop { break 'a; }` and `'b: loop { break 'b; }`.
+    pub ignore_loop_labels: bool,
+    /// Whether identifiers are compared without their trailing digits, e.g. `tmp1` and `tmp42`.
+    pub strip_ident_numeric_suffix: bool,
+    /// Whether identifiers are compared regardless of a raw `r#` prefix, e.g. `r#type` and `type`.
+    pub raw_insensitive: bool,
+    /// Whether the renames of `use` items are ignored, e.g. `use foo::Bar as Baz;` and
+    /// `use foo::Bar;`.
+    pub ignore_use_rename: bool,
+    /// Whether a `-` or `+` followed by a number is a single token when splitting a stream into
+    /// its tokens, e.g. `-1` (see `get_next_joint_token_in_mode`).
+    ///
+    /// Together with the semantic mode, a unary `+` is ignored, e.g. `+5` and `5` are equal.
+    pub signed_numbers: bool,
+    /// Whether type ascriptions are ignored, e.g. the `: i32` of `const X: i32 = 5;`.
+    pub ignore_type_ascription: bool,
+    /// The number of levels of nested groups whose contents are compared, beyond which groups
+    /// are compared by their string representations, or `None` for no limit.
+    ///
+    /// E.g. with `Some(1)`, `(a [b])` and `(a [ b ])` are equal, while `(a [b c])` and
+    /// `(a [b, c])` are not.
+    pub max_depth: Option<usize>,
+    /// Whether the semantic mode compares string literals ignoring ASCII case, e.g. `"Hello"`
+    /// and `"hello"`. Has no effect without the semantic mode.
+    pub str_ignore_case: bool,
+    /// Whether `&'static str` and `&str` are compared as equal.
+    pub elide_static_str: bool,
+    /// Whether the struct updates of struct literals are ignored, e.g. the `..base` of
+    /// `Foo { a: 1, ..base }`.
+    pub ignore_struct_update: bool,
+    /// Whether fields of struct literals are compared regardless of whether they use the field
+    /// shorthand, e.g. `Foo { a }` and `Foo { a: a }`.
+    pub normalize_field_shorthand: bool,
+    /// Whether the `mut` of function parameters is ignored, e.g. `fn f(mut x: i32)` and
+    /// `fn f(x: i32)`.
+    pub ignore_param_mut: bool,
+    /// Whether the anonymous lifetime `'_` matches any lifetime, e.g. `Foo<'_>` and `Foo<'a>`.
+    pub anon_lifetime_wildcard: bool,
+    /// Whether the `mut` of `let` bindings is ignored, e.g. `let mut x = 1;` and `let x = 1;`.
+    pub ignore_let_mut: bool,
+    /// Whether the error types of `Result<T, E>` are ignored, e.g. `Result<u8, X>` and
+    /// `Result<u8, Y>`.
+    pub ignore_result_err: bool,
+    /// Whether `unsafe` blocks are compared as their contents, e.g. `unsafe { foo() }` and
+    /// `foo()`.
+    pub ignore_unsafe_blocks: bool,
+    /// Whether unit structs are compared regardless of whether their body is `;` or `{}`, e.g.
+    /// `struct Foo;` and `struct Foo {}`.
+    pub unit_struct_forms: bool,
+    /// Whether the `'static` bounds of generic parameters are ignored, e.g. the `'static` of
+    /// `fn f<T: 'static>()`.
+    pub ignore_static_bounds: bool,
+}
+
+///
+/// Decides whether two identifiers, literals, or punctuations are equal.
+///
+/// By default, identifiers are equal if they are the same identifier, literals if they are
+/// spelled the same, and punctuations if they are the same character, as in
+/// [`tokens_equal`](fn.tokens_equal.html). Implementors override the methods for the kinds of
+/// tokens they want to compare differently, and pass themselves to
+/// [`tokens_equal_with`](fn.tokens_equal_with.html).
+///
+pub trait TokenComparator {
+    ///
+    /// Returns whether the two given identifiers are equal.
+    ///
+    /// Within a procedural macro, `proc_macro2` compares identifiers through their string
+    /// representations, so this allocates like comparing the strings directly would.
+    ///
+    fn compare_ident(&self, lhs: &Ident, rhs: &Ident) -> bool {
+        lhs == rhs
+    }
+
+    ///
+    /// Returns whether the two given literals are equal.
+    ///
+    fn compare_literal(&self, lhs: &Literal, rhs: &Literal) -> bool {
+        lhs.to_string() == rhs.to_string()
+    }
+
+    ///
+    /// Returns whether the two given punctuations are equal.
+    ///
+    /// Only single characters are compared, as multi-character punctuation is compared
+    /// character by character.
+    ///
+    fn compare_punct(&self, lhs: &Punct, rhs: &Punct) -> bool {
+        lhs.as_char() == rhs.as_char()
+    }
+}
+
+///
+/// The comparator that compares tokens like [`tokens_equal`](fn.tokens_equal.html).
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultComparator;
+
+impl TokenComparator for DefaultComparator {}
+
+///
+/// Returns whether the two given joint tokens are equal.
+///
+/// A joint token is the result of `get_next_joint_token`.
+///
+fn joint_tokens_equal(
+    lhs: &[TokenTree],
+    rhs: &[TokenTree],
+    mode: &Mode,
+    comparator: &dyn TokenComparator,
+) -> bool {
+    lhs.len() == rhs.len()
+        && lhs
+            .iter()
+            .zip(rhs)
+            .all(|(lhs, rhs)| token_trees_equal(lhs, rhs, mode, comparator))
+}
+
+///
+/// Returns whether the two given token trees are equal.
+///
+/// Groups are equal if their delimiters are equal and their contents are equal
+/// (see `streams_equal`), or, beyond the mode's maximum depth, if their string representations
+/// are equal.
+/// In semantic mode, integer literals, and likewise float literals, are equal if their values
+/// and type suffixes are equal.
+/// In case_insensitive mode, identifiers are equal if they are equal ignoring ASCII case,
+/// and in strip_ident_numeric_suffix mode if they are equal without their trailing digits.
+/// Other identifiers, literals, and punctuations are compared by the given comparator,
+/// with punctuations also having to have the same spacing in exact mode.
+/// Other token trees are equal if their string representations are equal,
+/// ignoring surrounding whitespace.
+///
+fn token_trees_equal(
+    lhs: &TokenTree,
+    rhs: &TokenTree,
+    mode: &Mode,
+    comparator: &dyn TokenComparator,
+) -> bool {
+    if mode.strict && std::mem::discriminant(lhs) != std::mem::discriminant(rhs) {
+        return false;
+    }
+    match (lhs, rhs) {
+        (TokenTree::Literal(lhs), TokenTree::Literal(rhs))
+            if mode.normalize_stringify && stringify::is_string_literal(lhs) =>
+        {
+            stringify::stringified_equal(lhs, rhs, mode, comparator)
+        }
+        (TokenTree::Group(lhs), TokenTree::Group(rhs)) if mode.max_depth == Some(0) => {
+            lhs.to_string() == rhs.to_string()
+        }
+        (TokenTree::Group(lhs), TokenTree::Group(rhs)) => {
+            let nested = Mode {
+                max_depth: mode.max_depth.map(|depth| depth - 1),
+                ..mode.clone()
+            };
+            (mode.ignore_delimiters || lhs.delimiter() == rhs.delimiter())
+                && streams_equal(lhs.stream(), rhs.stream(), &nested, comparator)
+        }
+        (TokenTree::Literal(lhs), TokenTree::Literal(rhs))
+            if mode.semantic || mode.ignore_suffixes =>
+        {
+            literals_equal(&lhs.to_string(), &rhs.to_string(), mode)
+        }
+        (TokenTree::Ident(lhs), TokenTree::Ident(rhs))
+            if mode.case_insensitive || mode.strip_ident_numeric_suffix || mode.raw_insensitive =>
+        {
+            idents_equal(&lhs.to_string(), &rhs.to_string(), mode)
+        }
+        (TokenTree::Ident(lhs), TokenTree::Ident(rhs)) => comparator.compare_ident(lhs, rhs),
+        (TokenTree::Literal(lhs), TokenTree::Literal(rhs)) => comparator.compare_literal(lhs, rhs),
+        (TokenTree::Punct(lhs), TokenTree::Punct(rhs)) => {
+            comparator.compare_punct(lhs, rhs) && (!mode.exact || lhs.spacing() == rhs.spacing())
+        }
+        _ if mode.exact => lhs.to_string() == rhs.to_string(),
+        _ => lhs.to_string().trim() == rhs.to_string().trim(),
+    }
+}
+
+///
+/// Returns whether the two given identifiers are equal in the case_insensitive,
+/// strip_ident_numeric_suffix, or raw_insensitive modes.
+///
+/// In strip_ident_numeric_suffix mode, any trailing digits are removed before comparing.
+/// Since identifiers can't start with a digit, this never leaves an identifier empty.
+/// In raw_insensitive mode, any `r#` prefix is removed before comparing.
+///
+fn idents_equal(lhs: &str, rhs: &str, mode: &Mode) -> bool {
+    let (lhs, rhs) = if mode.raw_insensitive {
+        (
+            lhs.strip_prefix("r#").unwrap_or(lhs),
+            rhs.strip_prefix("r#").unwrap_or(rhs),
+        )
+    } else {
+        (lhs, rhs)
+    };
+    let digit = |c: char| c.is_ascii_digit();
+    let (lhs, rhs) = if mode.strip_ident_numeric_suffix {
+        (lhs.trim_end_matches(digit), rhs.trim_end_matches(digit))
+    } else {
+        (lhs, rhs)
+    };
+    if mode.case_insensitive {
+        lhs.eq_ignore_ascii_case(rhs)
+    } else {
+        lhs == rhs
+    }
+}
+
+///
+/// Returns whether the two given token streams are equal.
+///
+/// The streams are equal if they contain the same number of joint tokens and
+/// each pair of joint tokens is equal (see `joint_tokens_equal`).
+/// In glob_matches_explicit mode, a glob import may also match a brace group
+/// (see `glob_matches_explicit`), and in anon_lifetime_wildcard mode, `'_` matches any
+/// lifetime (see `anon_lifetime_matches`).
+/// Both streams are first normalized according to the mode (see `normalize`).
+///
+fn streams_equal(
+    lhs: TokenStream,
+    rhs: TokenStream,
+    mode: &Mode,
+    comparator: &dyn TokenComparator,
+) -> bool {
+    let mut lhs = normalize(lhs, mode).peekable();
+    let mut rhs = normalize(rhs, mode).peekable();
+    let mut after_separator = false;
+    loop {
+        match (
+            get_next_joint_token(&mut lhs),
+            get_next_joint_token(&mut rhs),
+        ) {
+            (Some(lhs_token), Some(rhs_token)) => {
+                if mode.glob_matches_explicit
+                    && (glob_matches_explicit(&lhs_token, &rhs_token, &mut rhs, after_separator)
+                        || glob_matches_explicit(&rhs_token, &lhs_token, &mut lhs, after_separator))
+                {
+                    after_separator = false;
+                } else if joint_tokens_equal(&lhs_token, &rhs_token, mode, comparator)
+                    || (mode.anon_lifetime_wildcard
+                        && anon_lifetime_matches(&lhs_token, &rhs_token))
+                {
+                    after_separator =
+                        mode.glob_matches_explicit && token_strings(&lhs_token).concat() == "::";
+                } else {
+                    return false;
+                }
+            }
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+///
+/// Rewrites the given stream, without descending into groups, such that streams that are
+/// equal in the given mode become identical, returning its tokens:
+/// - Unless in strict mode, undelimited groups of a single token tree are unwrapped
+///   (see `peel_undelimited`).
+/// - In ignore_unsafe_blocks mode, `unsafe` blocks are replaced with their contents
+///   (see `blocks::unwrap_unsafe_blocks`).
+/// - In normalize_bounds_position mode, bounds are moved to where clauses
+///   (see `bounds::normalize_bounds_position`).
+/// - In concat_doc_attrs mode, doc attributes are concatenated (see `docs::concat_doc_attrs`).
+/// - In ignore_const_fn mode, the `const` of `const fn` is removed
+///   (see `qualifiers::strip_const_fn`).
+/// - In normalize_extern_crate mode, the renames of `extern crate` items are removed
+///   (see `extern_crate::strip_extern_crate_rename`).
+/// - In variant_fields_positional mode, struct variants are turned into tuple variants
+///   (see `variants::positional_variant_fields`).
+/// - In desugar_try mode, the canonical desugarings of `?` are replaced by `?`
+///   (see `desugar::sugar_try`).
+/// - In unrooted_attr_paths mode, the leading `::` of attribute paths is removed
+///   (see `paths::strip_attr_path_root`).
+/// - In assoc_bindings_unordered mode, associated type bindings are sorted
+///   (see `generics::sort_assoc_bindings`).
+/// - In ignore_impl_generics_names mode, the generic parameters of impl blocks are renamed
+///   by their position (see `impls::rename_impl_generics`).
+/// - In compare_impl_subject_only mode, impl blocks are reduced to their subjects
+///   (see `impls::truncate_to_impl_subject`).
+/// - In ignore_loop_labels mode, all labels are given the same name
+///   (see `labels::rename_labels`).
+/// - In ignore_use_rename mode, the renames of `use` items are removed
+///   (see `uses::strip_use_renames`).
+/// - In ignore_type_ascription mode, type ascriptions are removed
+///   (see `ascription::strip_type_ascriptions`).
+/// - In elide_static_str mode, the `'static` of `&'static str` is removed
+///   (see `lifetimes::elide_static_str`).
+/// - In semantic and signed_numbers mode, unary `+` signs of numbers are removed
+///   (see `numbers::strip_unary_plus`).
+/// - In ignore_struct_update mode, the struct updates of struct literals are removed
+///   (see `structs::strip_struct_updates`).
+/// - In normalize_field_shorthand mode, fields assigned variables of the same name are
+///   collapsed into the field shorthand (see `structs::collapse_field_shorthand`).
+/// - In ignore_param_mut mode, the `mut` of function parameters is removed
+///   (see `bindings::strip_param_mut`).
+/// - In ignore_let_mut mode, the `mut` of `let` bindings is removed
+///   (see `bindings::strip_let_mut`).
+/// - In ignore_result_err mode, the error types of `Result<T, E>` are replaced by `_`
+///   (see `generics::replace_result_errs`).
+/// - In unit_struct_forms mode, the `;` bodies of unit structs are replaced with `{}`
+///   (see `structs::unit_struct_braces`).
+/// - In ignore_static_bounds mode, the `'static` bounds of generic parameters are removed
+///   (see `lifetimes::strip_static_bounds`).
+///
+fn normalize(stream: TokenStream, mode: &Mode) -> Box<dyn Iterator<Item = TokenTree>> {
+    let strict = mode.strict;
+    let tokens = stream
+        .into_iter()
+        .map(move |tt| if strict { tt } else { peel_undelimited(tt) });
+    if !(mode.normalize_bounds_position
+        || mode.concat_doc_attrs
+        || mode.ignore_const_fn
+        || mode.normalize_extern_crate
+        || mode.variant_fields_positional
+        || mode.desugar_try
+        || mode.unrooted_attr_paths
+        || mode.assoc_bindings_unordered
+        || mode.ignore_impl_generics_names
+        || mode.compare_impl_subject_only
+        || mode.ignore_loop_labels
+        || mode.ignore_use_rename
+        || mode.ignore_type_ascription
+        || mode.elide_static_str
+        || (mode.semantic && mode.signed_numbers)
+        || mode.ignore_struct_update
+        || mode.normalize_field_shorthand
+        || mode.ignore_param_mut
+        || mode.ignore_let_mut
+        || mode.ignore_result_err
+        || mode.ignore_unsafe_blocks
+        || mode.unit_struct_forms
+        || mode.ignore_static_bounds)
+    {
+        // Nothing to rewrite, so the tokens can be compared as they are pulled from the stream.
+        return Box::new(tokens);
+    }
+    let mut stream: TokenStream = tokens.collect();
+    // The contents of unwrapped blocks become part of the stream, so they must be unwrapped
+    // before the other rewrites for those to apply to them too.
+    if mode.ignore_unsafe_blocks {
+        stream = blocks::unwrap_unsafe_blocks(stream);
+    }
+    if mode.normalize_bounds_position {
+        stream = bounds::normalize_bounds_position(stream);
+    }
+    if mode.concat_doc_attrs {
+        stream = docs::concat_doc_attrs(stream);
+    }
+    if mode.ignore_const_fn {
+        stream = qualifiers::strip_const_fn(stream);
+    }
+    if mode.normalize_extern_crate {
+        stream = extern_crate::strip_extern_crate_rename(stream);
+    }
+    if mode.variant_fields_positional {
+        stream = variants::positional_variant_fields(stream);
+    }
+    if mode.desugar_try {
+        stream = desugar::sugar_try(stream);
+    }
+    if mode.unrooted_attr_paths {
+        stream = paths::strip_attr_path_root(stream);
+    }
+    if mode.assoc_bindings_unordered {
+        stream = generics::sort_assoc_bindings(stream);
+    }
+    if mode.ignore_impl_generics_names {
+        stream = impls::rename_impl_generics(stream);
+    }
+    if mode.compare_impl_subject_only {
+        stream = impls::truncate_to_impl_subject(stream);
+    }
+    if mode.ignore_loop_labels {
+        stream = labels::rename_labels(stream);
+    }
+    if mode.ignore_use_rename {
+        stream = uses::strip_use_renames(stream);
+    }
+    if mode.ignore_type_ascription {
+        stream = ascription::strip_type_ascriptions(stream);
+    }
+    if mode.elide_static_str {
+        stream = lifetimes::elide_static_str(stream);
+    }
+    if mode.semantic && mode.signed_numbers {
+        stream = numbers::strip_unary_plus(stream);
+    }
+    if mode.ignore_struct_update {
+        stream = structs::strip_struct_updates(stream);
+    }
+    if mode.normalize_field_shorthand {
+        stream = structs::collapse_field_shorthand(stream);
+    }
+    if mode.ignore_param_mut {
+        stream = bindings::strip_param_mut(stream);
+    }
+    if mode.ignore_let_mut {
+        stream = bindings::strip_let_mut(stream);
+    }
+    if mode.ignore_result_err {
+        stream = generics::replace_result_errs(stream);
+    }
+    if mode.unit_struct_forms {
+        stream = structs::unit_struct_braces(stream);
+    }
+    if mode.ignore_static_bounds {
+        stream = lifetimes::strip_static_bounds(stream);
+    }
+    Box::new(stream.into_iter())
+}
+
+///
+/// If the given token tree is a group that has no delimiter and holds a single token tree,
+/// returns that token tree instead, recursively, such that e.g. a `$x:expr` metavariable that
+/// `macro_rules!` has wrapped in such a group is compared like the token tree it holds.
+///
+fn peel_undelimited(tt: TokenTree) -> TokenTree {
+    match tt {
+        TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+            let mut contents = group.stream().into_iter();
+            match (contents.next(), contents.next()) {
+                (Some(single), None) => peel_undelimited(single),
+                _ => TokenTree::Group(group),
+            }
+        }
+        tt => tt,
+    }
+}
+
+///
+/// Returns whether the given glob joint token matches the given explicit joint token,
+/// consuming the rest of the explicit import from the explicit stream if so.
+///
+/// The glob matches if:
+/// - it is `::*` and the explicit token is `::` followed by a brace group, or
+/// - it is `*`, the explicit token is a brace group, and both directly follow `::`.
+///
+/// Any punctuation joined after the `*` must equal the joint token following the brace group.
+///
+fn glob_matches_explicit(
+    glob: &[TokenTree],
+    explicit: &[TokenTree],
+    explicit_stream: &mut Peekable<impl Iterator<Item = TokenTree>>,
+    after_separator: bool,
+) -> bool {
+    let glob = token_strings(glob);
+    let is_brace = |tt: Option<&TokenTree>| match tt {
+        Some(TokenTree::Group(group)) => group.delimiter() == Delimiter::Brace,
+        _ => false,
+    };
+    let glob_rest = if glob.len() >= 3 && glob[..3] == [":", ":", "*"] {
+        if token_strings(explicit).concat() != "::" || !is_brace(explicit_stream.peek()) {
+            return false;
+        }
+        explicit_stream.next();
+        &glob[3..]
+    } else if !glob.is_empty() && glob[0] == "*" {
+        if !after_separator || explicit.len() != 1 || !is_brace(explicit.first()) {
+            return false;
+        }
+        &glob[1..]
+    } else {
+        return false;
+    };
+    glob_rest.is_empty()
+        || get_next_joint_token(explicit_stream)
+            .is_some_and(|next| token_strings(&next) == glob_rest)
+}
+
+///
+/// Returns whether the two given joint tokens are lifetimes of which at least one is
+/// the anonymous lifetime `'_`.
+///
+fn anon_lifetime_matches(lhs: &[TokenTree], rhs: &[TokenTree]) -> bool {
+    let lifetime_name = |token: &[TokenTree]| match token {
+        [TokenTree::Punct(apostrophe), TokenTree::Ident(name)] if apostrophe.as_char() == '\'' => {
+            Some(name.to_string())
+        }
+        _ => None,
+    };
+    match (lifetime_name(lhs), lifetime_name(rhs)) {
+        (Some(lhs), Some(rhs)) => lhs == "_" || rhs == "_",
+        _ => false,
+    }
+}
+
+///
+/// Returns the trimmed string representation of each of the given token trees.
+///
+/// Used by `tt-equal` and not part of the public API.
+///
+#[doc(hidden)]
+pub fn token_strings(tokens: &[TokenTree]) -> Vec<String> {
+    tokens
+        .iter()
+        .map(|tt| tt.to_string().trim().to_string())
+        .collect()
+}
+
+///
+/// Returns whether the given token is an identifier with the given name.
+///
+fn is_ident(tt: &TokenTree, name: &str) -> bool {
+    match tt {
+        TokenTree::Ident(ident) => ident == name,
+        _ => false,
+    }
+}
+
+///
+/// Tries to get the next token from the token stream iterator.
+///
+/// If no token is available, `None` is returned.
+///
+/// If the token is a multi-character punctuation, all the token in the punctuation are turned.
+/// I.e:
+///   * '+' will be returned as `Vec['+']`.
+///   * `+=` will be returned as `Vec['+', '=']`.
+///   * `..=` will be returned as `Vec['.', '.', '=']`.
+///
+/// A joint punctuation that isn't followed by another punctuation ends the multi-character
+/// punctuation.
+///
+/// A lifetime's apostrophe is returned together with the identifier following it,
+/// such that e.g. `'a` is a single token.
+///
+/// For other non-punctuation tokens, the vec will always contain 1 token.
+///
+/// Used by `tt-equal` and not part of the public API.
+///
+#[doc(hidden)]
+pub fn get_next_joint_token(
+    stream: &mut Peekable<impl Iterator<Item = TokenTree>>,
+) -> Option<Vec<TokenTree>> {
+    get_next_joint_token_in_mode(stream, &Mode::default())
+}
+
+///
+/// Tries to get the next token from the token stream iterator, like `get_next_joint_token`.
+///
+/// In signed_numbers mode, a lone `-` or `+` followed by a numeric literal is returned together
+/// with the literal, such that e.g. `-1` is a single token, regardless of whether it is
+/// written as `- 1`.
+///
+/// Used by `tt-equal` and not part of the public API.
+///
+#[doc(hidden)]
+pub fn get_next_joint_token_in_mode(
+    stream: &mut Peekable<impl Iterator<Item = TokenTree>>,
+    mode: &Mode,
+) -> Option<Vec<TokenTree>> {
+    let first = stream.next()?;
+    if let TokenTree::Punct(last) = first {
+        let mut tokens = vec![last];
+        while let Spacing::Joint = tokens.last().unwrap().spacing() {
+            // A punctuation passed through a macro keeps the spacing it was written with,
+            // so it may be joint even though it ends the stream.
+            if let Some(TokenTree::Punct(p)) = stream.peek() {
+                tokens.push(p.clone());
+                stream.next();
+            } else {
+                break;
+            }
+        }
+        let sign = mode.signed_numbers
+            && tokens.len() == 1
+            && (tokens[0].as_char() == '-' || tokens[0].as_char() == '+');
+        let lifetime = tokens.len() == 1 && tokens[0].as_char() == '\'';
+        let mut tokens: Vec<_> = tokens.into_iter().map(TokenTree::Punct).collect();
+        if lifetime {
+            if let Some(TokenTree::Ident(_)) = stream.peek() {
+                tokens.extend(stream.next());
+            }
+        }
+        if sign {
+            if let Some(TokenTree::Literal(lit)) = stream.peek() {
+                if lit.to_string().starts_with(|c: char| c.is_ascii_digit()) {
+                    tokens.extend(stream.next());
+                }
+            }
+        }
+        Some(tokens)
+    } else {
+        Some(vec![first])
+    }
+}
+
+///
+/// Returns whether the two given literals are equal in the semantic or ignore_suffixes modes.
+///
+/// In semantic mode, numbers are compared by their values and strings by the strings they
+/// represent, ignoring ASCII case in str_ignore_case mode, otherwise by their spelling.
+/// Characters, bytes, and byte strings are likewise compared by their values in semantic mode,
+/// but a literal of one kind is never equal to a literal of another, e.g. `'a'` and `b'a'`.
+/// In ignore_suffixes mode, the type suffixes of numbers are ignored, but an integer is
+/// never equal to a float.
+///
+fn literals_equal(lhs: &str, rhs: &str, mode: &Mode) -> bool {
+    if mode.semantic {
+        if let (Some(lhs), Some(rhs)) = (
+            docs::string_literal_value(lhs),
+            docs::string_literal_value(rhs),
+        ) {
+            let normalize = |s: String| {
+                if mode.normalize_line_endings {
+                    s.replace("\r\n", "\n").replace('\r', "\n")
+                } else {
+                    s
+                }
+            };
+            let (lhs, rhs) = (normalize(lhs), normalize(rhs));
+            return if mode.str_ignore_case {
+                lhs.eq_ignore_ascii_case(&rhs)
+            } else {
+                lhs == rhs
+            };
+        }
+        if let Some(equal) = decoded_equal(lhs, rhs, docs::char_literal_value)
+            .or_else(|| decoded_equal(lhs, rhs, docs::byte_literal_value))
+            .or_else(|| decoded_equal(lhs, rhs, docs::byte_string_literal_value))
+        {
+            return equal;
+        }
+    }
+    let spelling = |literal: &str, suffix: &str| {
+        literal
+            .strip_suffix(suffix)
+            .unwrap_or(literal)
+            .trim_end_matches('_')
+            .to_string()
+    };
+    match (parse_integer_literal(lhs), parse_integer_literal(rhs)) {
+        (Some((lhs_value, lhs_suffix)), Some((rhs_value, rhs_suffix))) => {
+            let values_equal = if mode.semantic {
+                lhs_value == rhs_value
+            } else {
+                spelling(lhs, &lhs_suffix) == spelling(rhs, &rhs_suffix)
+            };
+            let suffixes_equal = mode.ignore_suffixes
+                || if mode.semantic && mode.width_independent {
+                    integer_signedness(&lhs_suffix) == integer_signedness(&rhs_suffix)
+                } else {
+                    lhs_suffix == rhs_suffix
+                };
+            values_equal && suffixes_equal
+        }
+        (None, None) => match (parse_float_literal(lhs), parse_float_literal(rhs)) {
+            (Some((lhs_value, lhs_suffix)), Some((rhs_value, rhs_suffix))) => {
+                let values_equal = if mode.semantic {
+                    lhs_value == rhs_value
+                } else {
+                    spelling(lhs, &lhs_suffix) == spelling(rhs, &rhs_suffix)
+                };
+                values_equal && (mode.ignore_suffixes || lhs_suffix == rhs_suffix)
+            }
+            _ => lhs == rhs,
+        },
+        _ => lhs == rhs,
+    }
+}
+
+///
+/// Returns whether the two given literals have equal values if both can be decoded by
+/// the given function, otherwise `None`.
+///
+fn decoded_equal<T: PartialEq>(
+    lhs: &str,
+    rhs: &str,
+    decode: fn(&str) -> Option<T>,
+) -> Option<bool> {
+    Some(decode(lhs)? == decode(rhs)?)
+}
+
+///
+/// Returns the given integer type suffix without its width, e.g. `u` for `u8` and `usize`,
+/// such that only whether the type is signed remains.
+///
+fn integer_signedness(suffix: &str) -> &str {
+    suffix
+        .trim_end_matches("size")
+        .trim_end_matches(|c: char| c.is_ascii_digit())
+}
+
+///
+/// Parses the string representation of an integer literal into its value and its
+/// type suffix (e.g. `u8`), which is empty if the literal has none.
+///
+/// Underscores and the `0x`, `0o`, and `0b` base prefixes are accounted for.
+///
+/// If the string is not an integer literal, or the value doesn't fit in `u128`, `None` is returned.
+///
+/// Used by `tt-equal` and not part of the public API.
+///
+#[doc(hidden)]
+pub fn parse_integer_literal(literal: &str) -> Option<(u128, String)> {
+    let lit = literal.replace('_', "");
+    let (radix, digits) = match lit.get(..2) {
+        Some("0x") => (16, &lit[2..]),
+        Some("0o") => (8, &lit[2..]),
+        Some("0b") => (2, &lit[2..]),
+        _ => (10, &lit[..]),
+    };
+    let (digits, suffix) = digits.split_at(digits.find(['u', 'i']).unwrap_or(digits.len()));
+    u128::from_str_radix(digits, radix)
+        .ok()
+        .map(|value| (value, suffix.to_string()))
+}
+
+///
+/// Parses the string representation of a float literal into its value and its
+/// type suffix (e.g. `f32`), which is empty if the literal has none.
+///
+/// Underscores and exponents, signed or not (e.g. `1e3`, `1E+3`, and `1e-3`), are accounted for.
+///
+/// If the string is not a float literal, `None` is returned.
+/// This includes integer literals, i.e. those without a `.`, an exponent, or an `f` suffix,
+/// such that integers too large for `u128` are compared by their spelling rather than
+/// as floats.
+///
+fn parse_float_literal(literal: &str) -> Option<(f64, String)> {
+    let lit = literal.replace('_', "");
+    if !lit.starts_with(|c: char| c.is_ascii_digit()) || !lit.contains(['.', 'e', 'E', 'f']) {
+        return None;
+    }
+    let (digits, suffix) = lit.split_at(lit.find('f').unwrap_or(lit.len()));
+    digits.parse().ok().map(|value| (value, suffix.to_string()))
+}
@@ -0,0 +1,153 @@
+//!
+//! Normalization of lifetimes.
+//!
+use crate::bounds::{angle_bracket_depth_change, is_lone_punct, matching_angle_bracket};
+use crate::is_ident;
+use proc_macro2::{TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+///
+/// Removes the `'static` lifetime of every `&'static str` in the given stream, such that it
+/// becomes `&str`.
+///
+/// Only references to `str` are affected, such that e.g. `&'static [u8]` and
+/// `&'static String` are left as is.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn elide_static_str(stream: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        result.push(tokens[i].clone());
+        i += if is_static_str_reference(&tokens[i..]) {
+            3
+        } else {
+            1
+        };
+    }
+    TokenStream::from_iter(result)
+}
+
+///
+/// Returns whether the given tokens start with `&'static str`.
+///
+fn is_static_str_reference(tokens: &[TokenTree]) -> bool {
+    match tokens {
+        [TokenTree::Punct(ampersand), TokenTree::Punct(apostrophe), lifetime, ty, ..] => {
+            ampersand.as_char() == '&'
+                && apostrophe.as_char() == '\''
+                && is_ident(lifetime, "static")
+                && is_ident(ty, "str")
+        }
+        _ => false,
+    }
+}
+
+///
+/// The keywords that are followed by a name and then, optionally, generic parameters.
+///
+const NAMED_ITEM_KEYWORDS: &[&str] = &["enum", "fn", "struct", "trait", "type", "union"];
+
+///
+/// Removes the `'static` bounds of the generic parameters in the given stream, such that e.g.
+/// `fn f<T: 'static>()` becomes `fn f<T>()` and `fn f<T: Clone + 'static>()` becomes
+/// `fn f<T: Clone>()`.
+///
+/// Only the generic parameter lists of items and `impl`s are affected, and only bounds that
+/// are exactly `'static`, such that e.g. `<T: 'a>` and `<T = Box<dyn Any + 'static>>` are
+/// left as is.
+/// If a parameter is left without bounds, its `:` is removed too.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn strip_static_bounds(stream: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if starts_generic_params(&tokens, i) {
+            if let Some(end) = matching_angle_bracket(&tokens, i) {
+                result.push(tokens[i].clone());
+                result.extend(strip_static_bounds_of_params(&tokens[i + 1..end]));
+                result.push(tokens[end].clone());
+                i = end + 1;
+                continue;
+            }
+        }
+        result.push(tokens[i].clone());
+        i += 1;
+    }
+    TokenStream::from_iter(result)
+}
+
+///
+/// Returns whether the token at the given index is the `<` opening the generic parameters of
+/// an item or `impl`.
+///
+fn starts_generic_params(tokens: &[TokenTree], i: usize) -> bool {
+    match &tokens[..=i] {
+        [.., keyword, TokenTree::Punct(open)]
+            if open.as_char() == '<' && is_ident(keyword, "impl") =>
+        {
+            true
+        }
+        [.., keyword, TokenTree::Ident(_), TokenTree::Punct(open)] if open.as_char() == '<' => {
+            NAMED_ITEM_KEYWORDS.iter().any(|k| is_ident(keyword, k))
+        }
+        _ => false,
+    }
+}
+
+///
+/// Removes the `'static` bounds of the given generic parameters, excluding their angle
+/// brackets.
+///
+fn strip_static_bounds_of_params(params: &[TokenTree]) -> Vec<TokenTree> {
+    let mut result: Vec<TokenTree> = Vec::new();
+    let mut depth = 0;
+    let mut in_default = false;
+    let mut i = 0;
+    while i < params.len() {
+        if depth == 0 && !in_default && is_static_lifetime(&params[i..]) {
+            let last = result.len().wrapping_sub(1);
+            if !result.is_empty() && is_lone_punct(&result, last, '+') {
+                result.pop();
+                i += 2;
+                continue;
+            }
+            if !result.is_empty() && is_lone_punct(&result, last, ':') {
+                if i + 2 < params.len() && is_lone_punct(params, i + 2, '+') {
+                    // Other bounds follow, so the `:` is kept for them.
+                    i += 3;
+                } else {
+                    result.pop();
+                    i += 2;
+                }
+                continue;
+            }
+        }
+        depth += angle_bracket_depth_change(params, i, &params[i]);
+        if depth == 0 && is_lone_punct(params, i, '=') {
+            in_default = true;
+        } else if depth == 0 && is_lone_punct(params, i, ',') {
+            in_default = false;
+        }
+        result.push(params[i].clone());
+        i += 1;
+    }
+    result
+}
+
+///
+/// Returns whether the given tokens start with the `'static` lifetime.
+///
+fn is_static_lifetime(tokens: &[TokenTree]) -> bool {
+    match tokens {
+        [TokenTree::Punct(apostrophe), lifetime, ..] => {
+            apostrophe.as_char() == '\'' && is_ident(lifetime, "static")
+        }
+        _ => false,
+    }
+}
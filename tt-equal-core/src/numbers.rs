@@ -0,0 +1,44 @@
+//!
+//! Normalization of signed numbers.
+//!
+use proc_macro2::{Spacing, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+///
+/// Removes every unary `+` preceding a number in the given stream, such that e.g. `(+5)`
+/// becomes `(5)`.
+///
+/// A `+` is unary if it is the first token of the stream or follows a punctuation that isn't
+/// joint with it, such that e.g. the `+` of `a + 5` and `f() + 5` is left as is, while the
+/// second `+` of `a + +5` is removed.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn strip_unary_plus(stream: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    let result = tokens
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !is_unary_plus(&tokens, *i))
+        .map(|(_, tt)| tt.clone());
+    TokenStream::from_iter(result)
+}
+
+///
+/// Returns whether the token at the given index is a unary `+` followed by a number.
+///
+fn is_unary_plus(tokens: &[TokenTree], i: usize) -> bool {
+    let unary = match i.checked_sub(1).map(|prev| &tokens[prev]) {
+        None => true,
+        Some(TokenTree::Punct(prev)) => prev.spacing() == Spacing::Alone,
+        Some(_) => false,
+    };
+    match &tokens[i..] {
+        [TokenTree::Punct(plus), TokenTree::Literal(number), ..] => {
+            unary
+                && plus.as_char() == '+'
+                && number.to_string().starts_with(|c: char| c.is_ascii_digit())
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,61 @@
+//!
+//! Normalization of paths.
+//!
+use proc_macro2::{Delimiter, Group, Spacing, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+///
+/// Removes the leading `::` of the path of every attribute in the given stream, such that
+/// e.g. `#[::serde::Serialize]` becomes `#[serde::Serialize]`.
+///
+/// Both outer (`#[...]`) and inner (`#![...]`) attributes are normalized, but only their
+/// paths, e.g. the `::` of `#[derive(::serde::Serialize)]` is left as is.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn strip_attr_path_root(stream: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    let mut result = Vec::with_capacity(tokens.len());
+    for (i, tt) in tokens.iter().enumerate() {
+        match tt {
+            TokenTree::Group(group)
+                if group.delimiter() == Delimiter::Bracket && is_attr_start(&tokens[..i]) =>
+            {
+                let mut body = Vec::from_iter(group.stream());
+                if is_path_root(&body) {
+                    body.drain(..2);
+                }
+                let mut stripped = Group::new(Delimiter::Bracket, TokenStream::from_iter(body));
+                stripped.set_span(group.span());
+                result.push(TokenTree::Group(stripped));
+            }
+            _ => result.push(tt.clone()),
+        }
+    }
+    TokenStream::from_iter(result)
+}
+
+///
+/// Returns whether the given tokens end with the `#` or `#!` that starts an attribute.
+///
+fn is_attr_start(tokens: &[TokenTree]) -> bool {
+    match tokens {
+        [.., TokenTree::Punct(hash), TokenTree::Punct(bang)] if bang.as_char() == '!' => {
+            hash.as_char() == '#'
+        }
+        [.., TokenTree::Punct(hash)] => hash.as_char() == '#',
+        _ => false,
+    }
+}
+
+///
+/// Returns whether the given tokens start with `::`.
+///
+fn is_path_root(tokens: &[TokenTree]) -> bool {
+    match tokens {
+        [TokenTree::Punct(first), TokenTree::Punct(second), ..] => {
+            first.as_char() == ':' && first.spacing() == Spacing::Joint && second.as_char() == ':'
+        }
+        _ => false,
+    }
+}
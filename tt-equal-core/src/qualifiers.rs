@@ -0,0 +1,42 @@
+//!
+//! Normalization of the qualifiers of function signatures.
+//!
+use crate::is_ident;
+use proc_macro2::{TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+///
+/// Removes the `const` qualifier of every function in the given stream.
+///
+/// A `const` is only removed if it is followed by `fn`, possibly with other qualifiers
+/// (`async`, `unsafe`, and `extern` with an optional ABI) in between, such that e.g.
+/// `const X: u8` and `const N: usize` generic parameters are left as is.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn strip_const_fn(stream: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    let result = tokens
+        .iter()
+        .enumerate()
+        .filter(|(i, tt)| !(is_ident(tt, "const") && qualifies_fn(&tokens[i + 1..])))
+        .map(|(_, tt)| tt.clone());
+    TokenStream::from_iter(result)
+}
+
+///
+/// Returns whether the given tokens start with `fn`, possibly preceded by other qualifiers.
+///
+fn qualifies_fn(tokens: &[TokenTree]) -> bool {
+    for tt in tokens {
+        match tt {
+            TokenTree::Ident(ident) if ident == "fn" => return true,
+            TokenTree::Ident(ident)
+                if ident == "async" || ident == "unsafe" || ident == "extern" => {}
+            // The ABI of 'extern'
+            TokenTree::Literal(_) => {}
+            _ => return false,
+        }
+    }
+    false
+}
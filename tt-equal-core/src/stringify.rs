@@ -0,0 +1,41 @@
+//!
+//! Comparison of string literals holding stringified code.
+//!
+use crate::{docs::string_literal_value, streams_equal, Mode, TokenComparator};
+use proc_macro2::{Literal, TokenStream};
+
+///
+/// Returns whether the given literal is a string literal.
+///
+pub(crate) fn is_string_literal(literal: &Literal) -> bool {
+    string_literal_value(&literal.to_string()).is_some()
+}
+
+///
+/// Returns whether the two given literals are equal when the values of string literals are
+/// compared as code, e.g. `"(a, [b])"` and `"( a , [ b ] )"` are equal.
+///
+/// The values are parsed as token streams and compared recursively in the given mode,
+/// with the given comparator.
+/// If either value doesn't parse, the values are compared as they are instead.
+/// A string literal never equals a non-string literal.
+///
+pub(crate) fn stringified_equal(
+    lhs: &Literal,
+    rhs: &Literal,
+    mode: &Mode,
+    comparator: &dyn TokenComparator,
+) -> bool {
+    match (
+        string_literal_value(&lhs.to_string()),
+        string_literal_value(&rhs.to_string()),
+    ) {
+        (Some(lhs), Some(rhs)) => match (lhs.parse::<TokenStream>(), rhs.parse::<TokenStream>()) {
+            (Ok(lhs_stream), Ok(rhs_stream)) => {
+                streams_equal(lhs_stream, rhs_stream, mode, comparator)
+            }
+            _ => lhs == rhs,
+        },
+        _ => false,
+    }
+}
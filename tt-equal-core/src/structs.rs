@@ -0,0 +1,176 @@
+//!
+//! Normalization of structs and struct literals.
+//!
+use crate::bounds::matching_angle_bracket;
+use crate::is_ident;
+use proc_macro2::{Delimiter, Group, Spacing, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+///
+/// Removes the struct update of every struct literal in the given stream, such that e.g.
+/// `Foo { a: 1, ..base }` becomes `Foo { a: 1 }`.
+///
+/// A struct literal is taken to be an identifier followed by a brace group, and its struct
+/// update a `..` that starts the group or follows a `,` and is followed by an expression.
+/// A `..` followed by nothing, e.g. the rest of the pattern `Foo { a, .. }`, is left as is.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn strip_struct_updates(stream: TokenStream) -> TokenStream {
+    map_struct_literal_bodies(stream, |fields| {
+        let update = (0..fields.len()).find(|i| is_struct_update(&fields, *i));
+        match update {
+            Some(start) => {
+                let mut fields = fields[..start].to_vec();
+                if let Some(TokenTree::Punct(comma)) = fields.last() {
+                    if comma.as_char() == ',' {
+                        fields.pop();
+                    }
+                }
+                fields
+            }
+            None => fields,
+        }
+    })
+}
+
+///
+/// Collapses every field of every struct literal in the given stream that is assigned
+/// a variable of the same name into the field shorthand, such that e.g. `Foo { a: a, b: c }`
+/// becomes `Foo { a, b: c }`.
+///
+/// A struct literal is taken to be an identifier followed by a brace group, such that e.g.
+/// the struct pattern `Foo { a: a }` is also collapsed.
+///
+/// Nested groups are left as is, as they are normalized when they are compared themselves.
+///
+pub(crate) fn collapse_field_shorthand(stream: TokenStream) -> TokenStream {
+    map_struct_literal_bodies(stream, |fields| {
+        let mut result = Vec::new();
+        let mut i = 0;
+        while i < fields.len() {
+            result.push(fields[i].clone());
+            i += if is_explicit_shorthand(&fields, i) {
+                3
+            } else {
+                1
+            };
+        }
+        result
+    })
+}
+
+///
+/// Returns the given stream with the fields of every struct literal in it, i.e. the contents of
+/// every brace group following an identifier, replaced by the result of the given function.
+///
+fn map_struct_literal_bodies(
+    stream: TokenStream,
+    map: impl Fn(Vec<TokenTree>) -> Vec<TokenTree>,
+) -> TokenStream {
+    let tokens = Vec::from_iter(stream);
+    let mut result = Vec::new();
+    for (i, tt) in tokens.iter().enumerate() {
+        match tt {
+            TokenTree::Group(group)
+                if group.delimiter() == Delimiter::Brace
+                    && i > 0
+                    && matches!(tokens[i - 1], TokenTree::Ident(_)) =>
+            {
+                let fields = map(Vec::from_iter(group.stream()));
+                let mut body = Group::new(Delimiter::Brace, TokenStream::from_iter(fields));
+                body.set_span(group.span());
+                result.push(TokenTree::Group(body));
+            }
+            _ => result.push(tt.clone()),
+        }
+    }
+    TokenStream::from_iter(result)
+}
+
+///
+/// Returns whether the token at the given index starts a struct update, i.e. it is a `..`
+/// that starts the given fields or follows a `,`, and is followed by an expression.
+///
+fn is_struct_update(fields: &[TokenTree], i: usize) -> bool {
+    match &fields[i..] {
+        [TokenTree::Punct(first), TokenTree::Punct(second), _, ..] => {
+            starts_field(fields, i)
+                && first.as_char() == '.'
+                && first.spacing() == Spacing::Joint
+                && second.as_char() == '.'
+                && second.spacing() == Spacing::Alone
+        }
+        _ => false,
+    }
+}
+
+///
+/// Returns whether the token at the given index starts a field that is explicitly assigned
+/// a variable of the same name, i.e. it is `name: name` followed by a `,` or nothing.
+///
+fn is_explicit_shorthand(fields: &[TokenTree], i: usize) -> bool {
+    match &fields[i..] {
+        [TokenTree::Ident(name), TokenTree::Punct(colon), TokenTree::Ident(value), rest @ ..] => {
+            let ends_field = match rest.first() {
+                None => true,
+                Some(TokenTree::Punct(comma)) => comma.as_char() == ',',
+                Some(_) => false,
+            };
+            starts_field(fields, i)
+                && colon.as_char() == ':'
+                && colon.spacing() == Spacing::Alone
+                && name == value
+                && ends_field
+        }
+        _ => false,
+    }
+}
+
+///
+/// Returns whether the token at the given index starts a field, i.e. it starts the given fields
+/// or follows a `,`.
+///
+fn starts_field(fields: &[TokenTree], i: usize) -> bool {
+    match i.checked_sub(1).map(|prev| &fields[prev]) {
+        None => true,
+        Some(TokenTree::Punct(comma)) => comma.as_char() == ',',
+        Some(_) => false,
+    }
+}
+
+///
+/// Replaces the `;` body of every unit struct in the given stream with `{}`, such that e.g.
+/// `struct Foo;` becomes `struct Foo {}`.
+///
+/// Only a `;` directly following `struct`, the struct's name, and any generic parameters is
+/// a body, such that e.g. the `;` ending a tuple struct is left as is.
+/// A `;` is replaced rather than added, as it may be joint with a `>` preceding it, while
+/// a brace group is never joint with anything.
+///
+pub(crate) fn unit_struct_braces(stream: TokenStream) -> TokenStream {
+    let mut tokens = Vec::from_iter(stream);
+    for i in 0..tokens.len() {
+        if !(is_ident(&tokens[i], "struct")
+            && matches!(tokens.get(i + 1), Some(TokenTree::Ident(_))))
+        {
+            continue;
+        }
+        let body = match tokens.get(i + 2) {
+            Some(TokenTree::Punct(open)) if open.as_char() == '<' => {
+                matching_angle_bracket(&tokens, i + 2).map(|close| close + 1)
+            }
+            _ => Some(i + 2),
+        };
+        if let Some(body) = body {
+            if let Some(TokenTree::Punct(semicolon)) = tokens.get(body) {
+                if semicolon.as_char() == ';' {
+                    let mut braces = Group::new(Delimiter::Brace, TokenStream::new());
+                    braces.set_span(semicolon.span());
+                    tokens[body] = TokenTree::Group(braces);
+                }
+            }
+        }
+    }
+    TokenStream::from_iter(tokens)
+}
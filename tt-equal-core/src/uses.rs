@@ -0,0 +1,57 @@
+//!
+//! Normalization of `use` items.
+//!
+use crate::is_ident;
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+///
+/// Removes the renames of every `use` item in the given stream, such that e.g.
+/// `pub use foo::Bar as Baz;` becomes `pub use foo::Bar;` and `use foo::{a as b, c};`
+/// becomes `use foo::{a, c};`.
+///
+/// Only the tokens between `use` and the following `;` are affected, such that the `as` of
+/// e.g. `<T as Trait>::f()` or `x as u8` is left as is.
+///
+pub(crate) fn strip_use_renames(stream: TokenStream) -> TokenStream {
+    let mut tokens = Vec::new();
+    let mut in_use = false;
+    let mut iter = stream.into_iter();
+    while let Some(tt) = iter.next() {
+        match &tt {
+            TokenTree::Punct(semicolon) if semicolon.as_char() == ';' => in_use = false,
+            _ if is_ident(&tt, "use") => in_use = true,
+            _ if in_use => {
+                tokens.extend(strip_rename(tt, &mut iter));
+                continue;
+            }
+            _ => (),
+        }
+        tokens.push(tt);
+    }
+    TokenStream::from_iter(tokens)
+}
+
+///
+/// Returns the given token of a use tree with any renames in it removed, or `None` if the
+/// token is the `as` of a rename, in which case the new name is skipped too.
+///
+fn strip_rename(tt: TokenTree, rest: &mut impl Iterator<Item = TokenTree>) -> Option<TokenTree> {
+    match tt {
+        TokenTree::Ident(ident) if ident == "as" => {
+            rest.next();
+            None
+        }
+        TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+            let mut trees = group.stream().into_iter();
+            let mut stripped = Vec::new();
+            while let Some(tt) = trees.next() {
+                stripped.extend(strip_rename(tt, &mut trees));
+            }
+            let mut stripped_group = Group::new(Delimiter::Brace, TokenStream::from_iter(stripped));
+            stripped_group.set_span(group.span());
+            Some(TokenTree::Group(stripped_group))
+        }
+        tt => Some(tt),
+    }
+}
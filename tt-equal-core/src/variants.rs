@@ -0,0 +1,101 @@
+//!
+//! Normalization of the fields of enum variants.
+//!
+use crate::bounds::{split_once_top_level, split_top_level};
+use crate::is_ident;
+use proc_macro2::{Delimiter, Group, Punct, Spacing, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+///
+/// Turns every struct variant of the enums in the given stream into a tuple variant with the
+/// same field types in the same order, such that e.g. `Variant { a: u8, b: u16 }` becomes
+/// `Variant(u8, u16)`.
+///
+/// Fields are thereby compared positionally: the field names are dropped and the types of
+/// the n-th fields of the two variants are compared, regardless of how they are named.
+///
+/// Only the variants in the body of an `enum` item are rewritten, i.e. the first brace group
+/// following `enum`, the enum's name, and any generic parameters or where clause.
+/// A struct variant is taken to be an identifier followed by a brace group of `name: Type`
+/// fields, such that e.g. struct items and struct literals are left as is.
+///
+/// Nested groups other than the bodies of enums are left as is, as they are normalized when
+/// they are compared themselves.
+///
+pub(crate) fn positional_variant_fields(stream: TokenStream) -> TokenStream {
+    let mut tokens = Vec::from_iter(stream);
+    for i in 0..tokens.len() {
+        if !(is_ident(&tokens[i], "enum") && matches!(tokens.get(i + 1), Some(TokenTree::Ident(_))))
+        {
+            continue;
+        }
+        let body = tokens[i + 2..].iter_mut().find(
+            |tt| matches!(tt, TokenTree::Group(group) if group.delimiter() == Delimiter::Brace),
+        );
+        if let Some(TokenTree::Group(group)) = body {
+            let mut variants = Group::new(Delimiter::Brace, positional_variants(group.stream()));
+            variants.set_span(group.span());
+            *group = variants;
+        }
+    }
+    TokenStream::from_iter(tokens)
+}
+
+///
+/// Turns the given variants of an enum's body into tuple variants where they are struct
+/// variants.
+///
+fn positional_variants(variants: TokenStream) -> TokenStream {
+    let tokens = Vec::from_iter(variants);
+    let mut result = Vec::new();
+    for (i, tt) in tokens.iter().enumerate() {
+        match tt {
+            TokenTree::Group(group)
+                if group.delimiter() == Delimiter::Brace
+                    && i > 0
+                    && matches!(tokens[i - 1], TokenTree::Ident(_)) =>
+            {
+                match field_types(group.stream()) {
+                    Some(types) => {
+                        let mut tuple = Group::new(Delimiter::Parenthesis, types);
+                        tuple.set_span(group.span());
+                        result.push(TokenTree::Group(tuple));
+                    }
+                    None => result.push(tt.clone()),
+                }
+            }
+            _ => result.push(tt.clone()),
+        }
+    }
+    TokenStream::from_iter(result)
+}
+
+///
+/// Returns the comma-separated types of the given `name: Type` fields, keeping any
+/// attributes of the fields.
+///
+/// If any field isn't of that form, `None` is returned.
+///
+fn field_types(fields: TokenStream) -> Option<TokenStream> {
+    let fields = Vec::from_iter(fields);
+    let mut result = Vec::new();
+    for (n, field) in split_top_level(&fields, ',').iter().enumerate() {
+        let mut attributes_end = 0;
+        while let [TokenTree::Punct(hash), TokenTree::Group(body), ..] = &field[attributes_end..] {
+            if hash.as_char() != '#' || body.delimiter() != Delimiter::Bracket {
+                break;
+            }
+            attributes_end += 2;
+        }
+        let (name, ty) = split_once_top_level(&field[attributes_end..], ':')?;
+        if !matches!(name, [TokenTree::Ident(_)]) || ty.is_empty() {
+            return None;
+        }
+        if n > 0 {
+            result.push(TokenTree::Punct(Punct::new(',', Spacing::Alone)));
+        }
+        result.extend_from_slice(&field[..attributes_end]);
+        result.extend_from_slice(ty);
+    }
+    Some(TokenStream::from_iter(result))
+}
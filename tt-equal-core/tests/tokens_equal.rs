@@ -0,0 +1,375 @@
+//!
+//! Tests `tokens_equal` directly on token streams, outside of any procedural macro.
+//!
+use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use std::iter::FromIterator;
+use tt_equal_core::{
+    get_next_joint_token, get_next_joint_token_in_mode, tokens_equal, tokens_equal_in_mode,
+    tokens_equal_with, DefaultComparator, Mode, TokenComparator,
+};
+
+///
+/// Parses the given string into a token stream.
+///
+fn parse(s: &str) -> TokenStream {
+    s.parse().unwrap()
+}
+
+///
+/// Tests that identical and differently spaced streams are equal, while different streams aren't.
+///
+#[test]
+fn test_parsed_streams() {
+    assert!(tokens_equal(&parse("a"), &parse("a")));
+    assert!(tokens_equal(&parse("(a, b)"), &parse("( a ,b )")));
+    assert!(tokens_equal(&parse(""), &parse("")));
+    assert!(!tokens_equal(&parse("a"), &parse("b")));
+    assert!(!tokens_equal(&parse("(a)"), &parse("[a]")));
+    assert!(!tokens_equal(&parse("a b"), &parse("a")));
+    assert!(!tokens_equal(&parse("+="), &parse("+ =")));
+    assert!(tokens_equal(&parse("'a"), &parse("'a")));
+    assert!(!tokens_equal(&parse("'a"), &parse("'b")));
+}
+
+///
+/// Tests streams constructed from individual token trees.
+///
+#[test]
+fn test_constructed_streams() {
+    let group = |delimiter, stream| TokenTree::Group(Group::new(delimiter, stream));
+    let ident = |name| TokenTree::Ident(Ident::new(name, Span::call_site()));
+    let punct = |c, spacing| TokenTree::Punct(Punct::new(c, spacing));
+
+    let constructed = TokenStream::from_iter(vec![
+        ident("x"),
+        punct('+', Spacing::Joint),
+        punct('=', Spacing::Alone),
+        group(
+            Delimiter::Parenthesis,
+            TokenStream::from_iter(vec![TokenTree::Literal(Literal::u8_suffixed(1))]),
+        ),
+    ]);
+    assert!(tokens_equal(&constructed, &parse("x += (1u8)")));
+    assert!(!tokens_equal(&constructed, &parse("x + = (1u8)")));
+    assert!(!tokens_equal(&constructed, &parse("x += [1u8]")));
+
+    // A joint punctuation at the end of a stream is compared like an alone one.
+    let trailing_joint = TokenStream::from_iter(vec![punct('+', Spacing::Joint)]);
+    assert!(tokens_equal(&trailing_joint, &parse("+")));
+    let mut trailing_joint_tokens = trailing_joint.clone().into_iter().peekable();
+    assert_eq!(
+        get_next_joint_token(&mut trailing_joint_tokens).map(|token| token.len()),
+        Some(1)
+    );
+    assert!(get_next_joint_token(&mut trailing_joint_tokens).is_none());
+    let exact = Mode {
+        exact: true,
+        ..Mode::default()
+    };
+    assert!(!tokens_equal_in_mode(&trailing_joint, &parse("+"), &exact));
+
+    // An undelimited group is compared like its contents, unless in strict mode.
+    let undelimited = TokenStream::from_iter(vec![group(
+        Delimiter::None,
+        TokenStream::from_iter(vec![ident("x")]),
+    )]);
+    let strict = Mode {
+        strict: true,
+        ..Mode::default()
+    };
+    assert!(tokens_equal(&undelimited, &parse("x")));
+    assert!(!tokens_equal_in_mode(&undelimited, &parse("x"), &strict));
+    assert!(tokens_equal_in_mode(&undelimited, &undelimited, &strict));
+
+    // Undelimited groups of a single token tree are unwrapped, even when nested, so they
+    // combine with the tokens around them like the token tree they hold.
+    let undelimited_literal = |literal| {
+        group(
+            Delimiter::None,
+            TokenStream::from_iter(vec![TokenTree::Literal(literal)]),
+        )
+    };
+    let negative = TokenStream::from_iter(vec![
+        punct('-', Spacing::Alone),
+        group(
+            Delimiter::None,
+            TokenStream::from_iter(vec![undelimited_literal(Literal::u8_unsuffixed(1))]),
+        ),
+    ]);
+    assert!(tokens_equal(&negative, &parse("-1")));
+    assert!(!tokens_equal_in_mode(&negative, &parse("-1"), &strict));
+    let semantic = Mode {
+        semantic: true,
+        ..Mode::default()
+    };
+    let hex = TokenStream::from_iter(vec![undelimited_literal(
+        "0x10".parse::<Literal>().unwrap(),
+    )]);
+    assert!(tokens_equal_in_mode(&hex, &parse("16"), &semantic));
+    let undelimited_pair = TokenStream::from_iter(vec![group(Delimiter::None, parse("x y"))]);
+    assert!(!tokens_equal(&undelimited_pair, &parse("x")));
+}
+
+///
+/// Tests that the modes apply when comparing directly.
+///
+#[test]
+fn test_modes() {
+    let semantic = Mode {
+        semantic: true,
+        ..Mode::default()
+    };
+    assert!(!tokens_equal(&parse("(1_000)"), &parse("(0x3E8)")));
+    assert!(tokens_equal_in_mode(
+        &parse("(1_000)"),
+        &parse("(0x3E8)"),
+        &semantic
+    ));
+    assert!(!tokens_equal_in_mode(
+        &parse("1u32"),
+        &parse("1"),
+        &semantic
+    ));
+
+    let glob = Mode {
+        glob_matches_explicit: true,
+        ..Mode::default()
+    };
+    assert!(tokens_equal_in_mode(
+        &parse("use foo::*;"),
+        &parse("use foo::{a};"),
+        &glob
+    ));
+    assert!(!tokens_equal_in_mode(
+        &parse("use foo::*;"),
+        &parse("use bar::{a};"),
+        &glob
+    ));
+
+    let delimiters = Mode {
+        ignore_delimiters: true,
+        ..Mode::default()
+    };
+    assert!(!tokens_equal(&parse("(a)"), &parse("[a]")));
+    assert!(tokens_equal_in_mode(
+        &parse("(a)"),
+        &parse("[a]"),
+        &delimiters
+    ));
+
+    let bounds = Mode {
+        normalize_bounds_position: true,
+        ..Mode::default()
+    };
+    let inline = parse("fn f<T: Into<u8>, U>(t: T) -> U {}");
+    let where_clause = parse("fn f<T, U>(t: T) -> U where T: Into<u8> {}");
+    assert!(!tokens_equal(&inline, &where_clause));
+    assert!(tokens_equal_in_mode(&inline, &where_clause, &bounds));
+    assert!(!tokens_equal_in_mode(
+        &inline,
+        &parse("fn f<T, U>(t: T) -> U where U: Into<u8> {}"),
+        &bounds
+    ));
+
+    let docs = Mode {
+        concat_doc_attrs: true,
+        ..Mode::default()
+    };
+    let lines = parse("/// First\n/// Second\nstruct S;");
+    let string = parse("#[doc = \" First\\n Second\"] struct S;");
+    assert!(!tokens_equal(&lines, &string));
+    assert!(tokens_equal_in_mode(&lines, &string, &docs));
+}
+
+///
+/// Tests that multi-character punctuation is a single joint token, while punctuation
+/// separated by whitespace is not.
+///
+#[test]
+fn test_joint_punctuation() {
+    let joint_tokens = |s: &str| {
+        let mut stream = parse(s).into_iter().peekable();
+        let mut tokens = Vec::new();
+        while let Some(token) = get_next_joint_token(&mut stream) {
+            tokens.push(String::from_iter(token.iter().map(|tt| tt.to_string())));
+        }
+        tokens
+    };
+    assert_eq!(joint_tokens("a + b"), ["a", "+", "b"]);
+    assert_eq!(joint_tokens("a += b"), ["a", "+=", "b"]);
+    assert_eq!(joint_tokens("a + = b"), ["a", "+", "=", "b"]);
+    assert_eq!(joint_tokens("a ..= b"), ["a", "..=", "b"]);
+    assert_eq!(joint_tokens("a .. = b"), ["a", "..", "=", "b"]);
+    assert_eq!(joint_tokens("..= +="), ["..=", "+="]);
+}
+
+///
+/// Tests that a sign is grouped with the number following it only in signed_numbers mode.
+///
+#[test]
+fn test_signed_numbers() {
+    let signed = Mode {
+        signed_numbers: true,
+        ..Mode::default()
+    };
+    let joint_tokens = |s: &str, mode: &Mode| {
+        let mut stream = parse(s).into_iter().peekable();
+        let mut tokens = Vec::new();
+        while let Some(token) = get_next_joint_token_in_mode(&mut stream, mode) {
+            tokens.push(TokenStream::from_iter(token).to_string());
+        }
+        tokens
+    };
+    assert_eq!(joint_tokens("-1 - 2", &signed), ["- 1", "- 2"]);
+    assert_eq!(joint_tokens("a -1.5 +3", &signed), ["a", "- 1.5", "+ 3"]);
+    assert_eq!(joint_tokens("-a", &signed), ["-", "a"]);
+    assert_eq!(joint_tokens("-= 1", &signed), ["-=", "1"]);
+    assert_eq!(joint_tokens("- 1", &Mode::default()), ["-", "1"]);
+    assert!(tokens_equal(&parse("(-1)"), &parse("(- 1)")));
+    assert!(!tokens_equal(&parse("(-1)"), &parse("(1)")));
+}
+
+///
+/// Tests that the comparison stops at the first difference instead of first going
+/// through both streams in full.
+///
+/// The streams differ at their first token, followed by groups nested so deeply that
+/// comparing them would overflow the stack.
+///
+#[test]
+fn test_early_exit() {
+    let deeply_nested = |first: &str| {
+        let mut nested = TokenStream::new();
+        for _ in 0..1_000_000 {
+            nested = TokenStream::from(TokenTree::Group(Group::new(Delimiter::Bracket, nested)));
+        }
+        let mut stream = parse(first);
+        stream.extend(nested);
+        stream
+    };
+    assert!(!tokens_equal(&deeply_nested("a"), &deeply_nested("b")));
+}
+
+///
+/// Tests that a lifetime's apostrophe is grouped with the identifier following it.
+///
+#[test]
+fn test_lifetimes() {
+    let mut stream = parse("'a 'b").into_iter().peekable();
+    let first = get_next_joint_token(&mut stream).unwrap();
+    let second = get_next_joint_token(&mut stream).unwrap();
+    assert_eq!(first.len(), 2);
+    assert_eq!(second.len(), 2);
+    assert!(get_next_joint_token(&mut stream).is_none());
+    assert!(tokens_equal(
+        &TokenStream::from_iter(first.clone()),
+        &parse("'a")
+    ));
+    assert!(!tokens_equal(
+        &TokenStream::from_iter(first),
+        &TokenStream::from_iter(second)
+    ));
+}
+
+///
+/// Tests that groups nested deeper than the maximum depth are compared by their string
+/// representations, to which no modes apply.
+///
+#[test]
+fn test_max_depth() {
+    let shallow = Mode {
+        semantic: true,
+        max_depth: Some(1),
+        ..Mode::default()
+    };
+    assert!(tokens_equal_in_mode(
+        &parse("0x1 (1 [a])"),
+        &parse("1 (0x1 [ a ])"),
+        &shallow
+    ));
+    assert!(!tokens_equal_in_mode(
+        &parse("(1 [0x1])"),
+        &parse("(1 [1])"),
+        &shallow
+    ));
+    let flat = Mode {
+        max_depth: Some(0),
+        ..shallow
+    };
+    assert!(!tokens_equal_in_mode(&parse("(0x1)"), &parse("(1)"), &flat));
+    assert!(tokens_equal_in_mode(&parse("0x1"), &parse("1"), &flat));
+}
+
+///
+/// Tests that the default comparator compares tokens like `tokens_equal`.
+///
+#[test]
+fn test_default_comparator() {
+    let pairs = [
+        ("(a, 1, +=)", "( a,1,+= )"),
+        ("(a, 1, +=)", "(b, 1, +=)"),
+        ("(a, 1, +=)", "(a, 0x1, +=)"),
+        ("(a, 1, +=)", "(a, 1, + =)"),
+        ("'a", "'b"),
+    ];
+    for (lhs, rhs) in pairs.iter() {
+        assert_eq!(
+            tokens_equal_with(&parse(lhs), &parse(rhs), &DefaultComparator),
+            tokens_equal(&parse(lhs), &parse(rhs)),
+            "{} and {}",
+            lhs,
+            rhs
+        );
+    }
+}
+
+///
+/// Tests that a custom comparator decides whether identifiers, literals, and punctuations are
+/// equal, while groups are still compared by their delimiters.
+///
+#[test]
+fn test_custom_comparator() {
+    struct Loose;
+    impl TokenComparator for Loose {
+        fn compare_ident(&self, lhs: &Ident, rhs: &Ident) -> bool {
+            lhs.to_string().to_lowercase() == rhs.to_string().to_lowercase()
+        }
+        fn compare_literal(&self, lhs: &Literal, rhs: &Literal) -> bool {
+            lhs.to_string().trim_end_matches("u8") == rhs.to_string().trim_end_matches("u8")
+        }
+        fn compare_punct(&self, lhs: &Punct, rhs: &Punct) -> bool {
+            lhs.as_char() == rhs.as_char()
+                || ",;".contains(lhs.as_char()) && ",;".contains(rhs.as_char())
+        }
+    }
+    assert!(tokens_equal_with(
+        &parse("(Foo, 1u8)"),
+        &parse("(foo; 1)"),
+        &Loose
+    ));
+    assert!(tokens_equal_with(
+        &parse("{'A: a}"),
+        &parse("{'a: A}"),
+        &Loose
+    ));
+    assert!(!tokens_equal_with(
+        &parse("(Foo, 1u8)"),
+        &parse("(bar, 1)"),
+        &Loose
+    ));
+    assert!(!tokens_equal_with(
+        &parse("(Foo, 1u8)"),
+        &parse("(foo, 2)"),
+        &Loose
+    ));
+    assert!(!tokens_equal_with(
+        &parse("(Foo, 1u8)"),
+        &parse("[foo, 1]"),
+        &Loose
+    ));
+    assert!(!tokens_equal_with(
+        &parse("a += b"),
+        &parse("a + b"),
+        &Loose
+    ));
+}